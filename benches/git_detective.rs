@@ -1,19 +1,86 @@
-use std::fs::remove_dir_all;
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::{Path, PathBuf};
 
 use criterion::{criterion_group, criterion_main, Criterion};
+use git2::{Repository, Signature};
+use rayon::ThreadPoolBuilder;
 
 use git_detective::GitDetective;
 
+const FIXTURE_AUTHORS: [(&str, &str); 4] = [
+    ("Alice", "alice@example.com"),
+    ("Bob", "bob@example.com"),
+    ("Carol", "carol@example.com"),
+    ("Dave", "dave@example.com"),
+];
+const FIXTURE_FILES: usize = 64;
+const FIXTURE_LINES: usize = 400;
+
+/// Create a local repository with many files, each written by every fixture author
+fn create_fixture(path: &Path) -> Result<(), git2::Error> {
+    let _ = remove_dir_all(path);
+    create_dir_all(path.join("src")).unwrap();
+    let repository = Repository::init(path)?;
+    let mut contents = vec![String::new(); FIXTURE_FILES];
+    let mut parent = None;
+    for (round, (name, email)) in FIXTURE_AUTHORS.iter().enumerate() {
+        let mut index = repository.index()?;
+        for (file, content) in contents.iter_mut().enumerate() {
+            for line in 0..FIXTURE_LINES / FIXTURE_AUTHORS.len() {
+                content.push_str(&format!("// {} line {} of round {}\n", name, line, round));
+                content.push_str(&format!("fn f_{}_{}_{}() {{}}\n", file, round, line));
+            }
+            let relative = PathBuf::from(format!("src/file_{}.rs", file));
+            write(path.join(&relative), &content).unwrap();
+            index.add_path(&relative)?;
+        }
+        index.write()?;
+        let tree = repository.find_tree(index.write_tree()?)?;
+        let signature = Signature::now(name, email)?;
+        let parents: Vec<_> = parent.iter().collect();
+        let id = repository.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &format!("Round {}", round),
+            &tree,
+            &parents,
+        )?;
+        parent = Some(repository.find_commit(id)?);
+    }
+    Ok(())
+}
+
 fn clap_benchmark(c: &mut Criterion) {
-    let mut gd = GitDetective::clone("https://github.com/serde-rs/serde", "serde", true).unwrap();
+    let gd = GitDetective::clone("https://github.com/serde-rs/serde", "serde", true).unwrap();
     c.bench_function("Final Contributions - Serde Benchmark", |b| {
         b.iter(|| gd.final_contributions())
     });
     remove_dir_all("serde").unwrap();
 }
 
+fn parallel_benchmark(c: &mut Criterion) {
+    let path = std::env::temp_dir().join("git_detective_bench_fixture");
+    create_fixture(&path).unwrap();
+    // GitDetective isn't Sync, so it's opened on the pool that runs it
+    let final_contributions = || GitDetective::open(&path).unwrap().final_contributions();
+    let single_thread = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+    let all_threads = ThreadPoolBuilder::new().build().unwrap();
+    c.bench_function("Final Contributions - Fixture Single Thread", |b| {
+        b.iter(|| single_thread.install(final_contributions))
+    });
+    c.bench_function("Final Contributions - Fixture All Threads", |b| {
+        b.iter(|| all_threads.install(final_contributions))
+    });
+    remove_dir_all(path).unwrap();
+}
+
 criterion_group!(name = final_contributions_clap;
                  config = Criterion::default().sample_size(10);
                  targets = clap_benchmark);
 
-criterion_main!(final_contributions_clap);
+criterion_group!(name = final_contributions_parallel;
+                 config = Criterion::default().sample_size(10);
+                 targets = parallel_benchmark);
+
+criterion_main!(final_contributions_parallel, final_contributions_clap);
@@ -5,7 +5,7 @@ use git_detective::{Error, GitDetective};
 fn main() -> Result<(), Error> {
     let path = "serde-example";
 
-    let mut gd = GitDetective::clone("https://github.com/serde-rs/serde.git", path, true)?;
+    let gd = GitDetective::clone("https://github.com/serde-rs/serde.git", path, true)?;
     let contributions = gd.final_contributions()?;
     println!("{:#?}", contributions);
     println!("Total lines = {}", contributions.total_lines());
@@ -16,6 +16,15 @@ pub fn clap() -> ArgMatches<'static> {
                 .help("Set log output level:\n\t1. TODO\n\t2. TODO\n\t3. TODO")
                 .multiple(true),
         )
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .takes_value(true)
+                .possible_values(&["table", "json", "csv", "tsv", "markdown"])
+                .default_value("table")
+                .global(true)
+                .help("Output format"),
+        )
         .subcommand(
             SubCommand::with_name("clone")
                 .about("Clone a remote repository to inspect")
@@ -70,6 +79,18 @@ pub fn clap() -> ArgMatches<'static> {
                         .conflicts_with_all(&["commits", "tags", "contributors"]),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("report")
+                .about("Write a report of all statistics to a single file")
+                .arg(
+                    Arg::with_name("html")
+                        .long("html")
+                        .takes_value(true)
+                        .value_name("path")
+                        .required(true)
+                        .help("Path to write a self-contained HTML report to"),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("statistics")
                 .about("Statistics about the Git Repository")
@@ -78,14 +99,52 @@ pub fn clap() -> ArgMatches<'static> {
                     Arg::with_name("final")
                         .long("final")
                         .help("Statistics in the most recent commit by language and contributor")
-                        .conflicts_with_all(&["files", "difference"]),
+                        .conflicts_with_all(&["files", "difference", "timeline", "per-tag"]),
                 )
                 .arg(
                     Arg::with_name("difference")
                         .short("d")
                         .long("diff")
                         .help("Insertion/Deletions by contributor")
-                        .conflicts_with_all(&["files", "final"]),
+                        .conflicts_with_all(&["files", "final", "timeline", "per-tag"]),
+                )
+                .arg(
+                    Arg::with_name("files")
+                        .short("f")
+                        .long("files")
+                        .help("Commits, Insertion/Deletions, and authors by file")
+                        .conflicts_with_all(&["final", "difference", "timeline", "per-tag"]),
+                )
+                .arg(
+                    Arg::with_name("timeline")
+                        .short("t")
+                        .long("timeline")
+                        .takes_value(true)
+                        .min_values(0)
+                        .possible_values(&["day", "week", "month"])
+                        .value_name("period")
+                        .help("Commits and Insertion/Deletions by contributor per day, week (default), or month")
+                        .conflicts_with_all(&["final", "difference", "files", "per-tag"]),
+                )
+                .arg(
+                    Arg::with_name("per-tag")
+                        .short("p")
+                        .long("per-tag")
+                        .help("Final contributions at every tag and their change since the previous tag")
+                        .conflicts_with_all(&["final", "difference", "files", "timeline"]),
+                )
+                .arg(
+                    Arg::with_name("languages")
+                        .short("l")
+                        .long("languages")
+                        .requires("difference")
+                        .help("Insertion/Deletions by contributor and language"),
+                )
+                .arg(
+                    Arg::with_name("ignore-whitespace")
+                        .short("w")
+                        .long("ignore-whitespace")
+                        .help("Ignore whitespace-only changes when crediting contributors"),
                 )
                 .arg(
                     Arg::with_name("name")
@@ -0,0 +1,381 @@
+//! CSV and TSV output for `--format csv` and `--format tsv`
+//!
+//! Every view is a header row followed by one row per record, fields are quoted like RFC 4180
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use git_detective::{
+    Activity, Branch, CommitRecord, Contributor, DiffStats, FileChurn, Period, ProjectStats,
+    Snapshot, TagRecord,
+};
+
+pub struct Delimited {
+    delimiter: char,
+    header: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Delimited {
+    pub fn new(delimiter: char, header: &[&'static str]) -> Self {
+        Self {
+            delimiter,
+            header: header.to_vec(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(&mut self, fields: Vec<String>) {
+        self.rows.push(fields);
+    }
+
+    // Quote fields containing the delimiter, quotes, or line breaks, doubling quotes inside
+    fn field(&self, f: &mut fmt::Formatter<'_>, field: &str) -> fmt::Result {
+        let delimiter = self.delimiter;
+        if field.contains(&[delimiter, '"', '\n', '\r'][..]) {
+            write!(f, "\"{}\"", field.replace('"', "\"\""))
+        } else {
+            write!(f, "{}", field)
+        }
+    }
+
+    fn line<S: AsRef<str>>(&self, f: &mut fmt::Formatter<'_>, fields: &[S]) -> fmt::Result {
+        for (index, field) in fields.iter().enumerate() {
+            if index > 0 {
+                write!(f, "{}", self.delimiter)?;
+            }
+            self.field(f, field.as_ref())?;
+        }
+        writeln!(f)
+    }
+}
+
+impl fmt::Display for Delimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.line(f, &self.header)?;
+        for row in self.rows.iter() {
+            self.line(f, row)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn final_contributions(stats: &ProjectStats, delimiter: char) -> Delimited {
+    let mut delimited = Delimited::new(
+        delimiter,
+        &[
+            "contributor",
+            "language",
+            "lines",
+            "code",
+            "comments",
+            "blanks",
+        ],
+    );
+    let mut contributions: Vec<_> = stats.iter().collect();
+    contributions.sort_unstable_by_key(|(author, _)| *author);
+    for (author, lang_map) in contributions {
+        let mut lang_stats: Vec<_> = lang_map.iter().collect();
+        lang_stats.sort_unstable_by_key(|(lang, _)| *lang);
+        for (lang, stats) in lang_stats {
+            delimited.row(vec![
+                author.to_string(),
+                lang.to_string(),
+                stats.lines.to_string(),
+                stats.code.to_string(),
+                stats.comments.to_string(),
+                stats.blanks.to_string(),
+            ]);
+        }
+    }
+    delimited
+}
+
+const DIFF_STATS_HEADER: [&str; 13] = [
+    "insertions",
+    "deletions",
+    "inserted_code",
+    "inserted_comments",
+    "inserted_blanks",
+    "deleted_code",
+    "deleted_comments",
+    "deleted_blanks",
+    "binary_files",
+    "binary_bytes",
+    "renames",
+    "copies",
+    "merges",
+];
+
+fn diff_stats_row(mut row: Vec<String>, stats: &DiffStats) -> Vec<String> {
+    row.extend(
+        [
+            stats.insertions,
+            stats.deletions,
+            stats.inserted.code,
+            stats.inserted.comments,
+            stats.inserted.blanks,
+            stats.deleted.code,
+            stats.deleted.comments,
+            stats.deleted.blanks,
+            stats.binary.added + stats.binary.modified,
+            stats.binary.bytes,
+            stats.renames,
+            stats.copies,
+            stats.merges,
+        ]
+        .iter()
+        .map(usize::to_string),
+    );
+    row
+}
+
+pub fn diff_stats(stats: &HashMap<Contributor, DiffStats>, delimiter: char) -> Delimited {
+    let mut header = vec!["contributor"];
+    header.extend_from_slice(&DIFF_STATS_HEADER);
+    let mut delimited = Delimited::new(delimiter, &header);
+    let mut authored_diffs: Vec<_> = stats.iter().collect();
+    authored_diffs.sort_unstable_by_key(|(author, _)| *author);
+    for (author, diff_stats) in authored_diffs {
+        delimited.row(diff_stats_row(vec![author.to_string()], diff_stats));
+    }
+    delimited
+}
+
+pub fn diff_stats_by_language(
+    stats: &HashMap<Contributor, DiffStats>,
+    delimiter: char,
+) -> Delimited {
+    let mut header = vec!["contributor", "language"];
+    header.extend_from_slice(&DIFF_STATS_HEADER);
+    let mut delimited = Delimited::new(delimiter, &header);
+    let mut authored_diffs: Vec<_> = stats.iter().collect();
+    authored_diffs.sort_unstable_by_key(|(author, _)| *author);
+    for (author, diff_stats) in authored_diffs {
+        for (language, language_stats) in diff_stats.languages.iter() {
+            let row = vec![author.to_string(), language.to_string()];
+            delimited.row(diff_stats_row(row, language_stats));
+        }
+    }
+    delimited
+}
+
+pub fn file_churn(churn: &HashMap<PathBuf, FileChurn>, delimiter: char) -> Delimited {
+    let mut delimited = Delimited::new(
+        delimiter,
+        &["path", "commits", "insertions", "deletions", "authors"],
+    );
+    let mut churn: Vec<_> = churn.iter().collect();
+    churn.sort_unstable_by_key(|(path, _)| *path);
+    for (path, file_churn) in churn {
+        delimited.row(vec![
+            path.to_string_lossy().to_string(),
+            file_churn.commits.to_string(),
+            file_churn.insertions.to_string(),
+            file_churn.deletions.to_string(),
+            file_churn.authors().to_string(),
+        ]);
+    }
+    delimited
+}
+
+pub fn timeline(
+    timeline: &BTreeMap<NaiveDate, HashMap<Contributor, Activity>>,
+    period: Period,
+    delimiter: char,
+) -> Delimited {
+    let mut delimited = Delimited::new(
+        delimiter,
+        &[
+            "period",
+            "start",
+            "contributor",
+            "commits",
+            "insertions",
+            "deletions",
+        ],
+    );
+    for (start, contributors) in timeline.iter() {
+        let mut contributors: Vec<_> = contributors.iter().collect();
+        contributors.sort_unstable_by_key(|(author, _)| *author);
+        for (author, activity) in contributors {
+            delimited.row(vec![
+                period.label(*start),
+                start.to_string(),
+                author.to_string(),
+                activity.commits.to_string(),
+                activity.insertions.to_string(),
+                activity.deletions.to_string(),
+            ]);
+        }
+    }
+    delimited
+}
+
+pub fn snapshots(snapshots: &[Snapshot], delimiter: char) -> Delimited {
+    let mut delimited = Delimited::new(
+        delimiter,
+        &[
+            "revision",
+            "date",
+            "contributor",
+            "lines",
+            "code",
+            "delta_lines",
+            "delta_code",
+        ],
+    );
+    for snapshot in snapshots {
+        let mut deltas: Vec<_> = snapshot.delta.iter().collect();
+        deltas.sort_unstable_by_key(|(author, _)| *author);
+        for (author, delta) in deltas {
+            let total = snapshot
+                .stats
+                .total_contribs_by_name(author.name())
+                .unwrap_or_default();
+            delimited.row(vec![
+                snapshot.revision.clone(),
+                snapshot.date.to_rfc3339(),
+                author.to_string(),
+                total.lines.to_string(),
+                total.code.to_string(),
+                delta.lines.to_string(),
+                delta.code.to_string(),
+            ]);
+        }
+    }
+    delimited
+}
+
+pub fn commits(commits: &[CommitRecord], delimiter: char) -> Delimited {
+    let mut delimited = Delimited::new(
+        delimiter,
+        &[
+            "id",
+            "author",
+            "author_email",
+            "committer",
+            "committer_email",
+            "date",
+            "summary",
+        ],
+    );
+    for commit in commits {
+        delimited.row(vec![
+            commit.id.clone(),
+            commit.author.name.clone(),
+            commit.author.email.clone(),
+            commit.committer.name.clone(),
+            commit.committer.email.clone(),
+            commit.date.to_rfc3339(),
+            commit.summary.clone().unwrap_or_default(),
+        ]);
+    }
+    delimited
+}
+
+pub fn tags(tags: &[TagRecord], delimiter: char) -> Delimited {
+    let mut delimited = Delimited::new(
+        delimiter,
+        &["name", "id", "tagger", "tagger_email", "date", "message"],
+    );
+    for tag in tags {
+        let (tagger, email, date) = match tag.tagger.as_ref() {
+            Some(tagger) => (
+                tagger.name.clone(),
+                tagger.email.clone(),
+                tagger.date.to_rfc3339(),
+            ),
+            None => (String::new(), String::new(), String::new()),
+        };
+        delimited.row(vec![
+            tag.name.clone(),
+            tag.id.clone(),
+            tagger,
+            email,
+            date,
+            // Messages end with a newline, which would quote every message
+            tag.message
+                .as_deref()
+                .unwrap_or_default()
+                .trim_end()
+                .to_string(),
+        ]);
+    }
+    delimited
+}
+
+pub fn branches<'repo>(
+    branches: impl Iterator<Item = Branch<'repo>>,
+    delimiter: char,
+) -> Delimited {
+    let mut delimited = Delimited::new(delimiter, &["name", "head"]);
+    for branch in branches {
+        if let Ok(name) = branch.name() {
+            delimited.row(vec![name, branch.is_head().to_string()]);
+        }
+    }
+    delimited
+}
+
+// Emails are separated by spaces, which can't be part of an email
+pub fn contributors(contributors: &HashSet<Contributor>, delimiter: char) -> Delimited {
+    let mut delimited = Delimited::new(delimiter, &["name", "emails"]);
+    let mut contributors: Vec<_> = contributors.iter().collect();
+    contributors.sort_unstable();
+    for contributor in contributors {
+        let emails: Vec<_> = contributor.emails().iter().map(String::as_str).collect();
+        delimited.row(vec![contributor.to_string(), emails.join(" ")]);
+    }
+    delimited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Delimited;
+
+    fn delimited(delimiter: char, name: &str) -> String {
+        let mut delimited = Delimited::new(delimiter, &["contributor", "lines"]);
+        delimited.row(vec![name.to_string(), "1".to_string()]);
+        delimited.to_string()
+    }
+
+    #[test]
+    fn comma() {
+        assert_eq!(
+            delimited(',', "Doe, Jane"),
+            "contributor,lines\n\"Doe, Jane\",1\n"
+        );
+    }
+
+    #[test]
+    fn quotes() {
+        assert_eq!(
+            delimited(',', "Jane \"JD\" Doe"),
+            "contributor,lines\n\"Jane \"\"JD\"\" Doe\",1\n"
+        );
+    }
+
+    #[test]
+    fn newline() {
+        assert_eq!(
+            delimited(',', "Jane\nDoe"),
+            "contributor,lines\n\"Jane\nDoe\",1\n"
+        );
+    }
+
+    #[test]
+    fn tab() {
+        assert_eq!(
+            delimited('\t', "Jane\tDoe"),
+            "contributor\tlines\n\"Jane\tDoe\"\t1\n"
+        );
+        // Commas only need quoting in CSV
+        assert_eq!(
+            delimited('\t', "Doe, Jane"),
+            "contributor\tlines\nDoe, Jane\t1\n"
+        );
+    }
+}
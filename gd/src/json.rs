@@ -0,0 +1,62 @@
+//! JSON output for `--format json`
+//!
+//! Maps are sorted by key and dates are RFC 3339, so output only changes with the repository
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::process;
+
+use chrono::NaiveDate;
+use git_detective::{Activity, Branch, Contributor, Period};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+pub fn print<T: Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            println!("JSON Error: `{}`", e);
+            process::exit(-1);
+        }
+    }
+}
+
+pub fn sorted<K: Ord, V>(map: &HashMap<K, V>) -> BTreeMap<&K, &V> {
+    map.iter().collect()
+}
+
+// [{"name": "Nick Hackman", "emails": ["snickhackman@gmail.com"]}]
+pub fn contributors(contributors: &HashSet<Contributor>) -> Value {
+    let mut contributors: Vec<_> = contributors.iter().collect();
+    contributors.sort_unstable();
+    contributors
+        .into_iter()
+        .map(|contributor| json!({ "name": contributor.name(), "emails": contributor.emails() }))
+        .collect()
+}
+
+// [{"name": "master", "head": true}]
+pub fn branches<'repo>(branches: impl Iterator<Item = Branch<'repo>>) -> Value {
+    branches
+        .filter_map(|branch| {
+            let name = branch.name().ok()?;
+            Some(json!({ "name": name, "head": branch.is_head() }))
+        })
+        .collect()
+}
+
+// [{"period": "2020-W20", "start": "2020-05-11", "contributors": {"Nick Hackman": Activity}}]
+pub fn timeline(
+    timeline: &BTreeMap<NaiveDate, HashMap<Contributor, Activity>>,
+    period: Period,
+) -> Value {
+    timeline
+        .iter()
+        .map(|(start, contributors)| {
+            json!({
+                "period": period.label(*start),
+                "start": start.to_string(),
+                "contributors": sorted(contributors),
+            })
+        })
+        .collect()
+}
@@ -9,16 +9,49 @@
     unused_must_use
 )]
 
-use std::process;
+use std::path::PathBuf;
+use std::{env, fs, process};
 
 use clap::ArgMatches;
-use git_detective::{Error, GitDetective};
+use git_detective::{CommitRecord, Error, GitDetective, Period, TagRecord, WhitespaceMode};
 
 mod cli;
 use cli::clap;
 
+mod json;
+
+mod delimited;
+
+mod report;
+use report::Report;
+
 mod table;
-use table::{CommitsTable, DiffStatsTable, FinalContributionsTable, TagsTable};
+use table::{
+    CommitsTable, DiffStatsTable, FileChurnTable, FinalContributionsTable, SnapshotsTable,
+    TagsTable, TimelineTable,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Format {
+    Table,
+    Json,
+    // CSV or TSV separated by the delimiter
+    Delimited(char),
+    // GitHub-flavoured Markdown, views without a Markdown renderer print their table
+    Markdown,
+}
+
+impl Format {
+    fn from_matches(matches: &ArgMatches) -> Self {
+        match matches.value_of("format") {
+            Some("json") => Format::Json,
+            Some("csv") => Format::Delimited(','),
+            Some("tsv") => Format::Delimited('\t'),
+            Some("markdown") => Format::Markdown,
+            _ => Format::Table,
+        }
+    }
+}
 
 fn construct_gd(matches: &ArgMatches) -> Result<GitDetective, Error> {
     let gd = match matches.subcommand() {
@@ -35,48 +68,173 @@ fn construct_gd(matches: &ArgMatches) -> Result<GitDetective, Error> {
 fn run(matches: ArgMatches) -> Result<(), Error> {
     let mut gd = construct_gd(&matches)?;
     match matches.subcommand() {
-        ("list", Some(list_args)) => Ok(list(list_args, &gd)?),
-        ("statistics", Some(stats_args)) => Ok(stats(stats_args, &mut gd)?),
+        ("list", Some(list_args)) => Ok(list(list_args, &gd, Format::from_matches(list_args))?),
+        ("statistics", Some(stats_args)) => {
+            if stats_args.is_present("ignore-whitespace") {
+                gd.set_whitespace_mode(WhitespaceMode::Insensitive);
+            }
+            Ok(stats(stats_args, &gd, Format::from_matches(stats_args))?)
+        }
+        ("report", Some(report_args)) => Ok(report(report_args, &gd)?),
         ("clone", _) => Ok(()),
         _ => unreachable!(),
     }
 }
 
-fn stats(matches: &ArgMatches, gd: &mut GitDetective) -> Result<(), Error> {
+fn stats(matches: &ArgMatches, gd: &GitDetective, format: Format) -> Result<(), Error> {
     let _name = matches.value_of("name");
     let dimensions = term_size::dimensions();
     if matches.is_present("difference") {
         let diff_stats = gd.diff_stats()?;
-        println!("{}", DiffStatsTable::new(diff_stats, dimensions));
+        let languages = matches.is_present("languages");
+        match format {
+            Format::Json => json::print(&json::sorted(&diff_stats)),
+            Format::Delimited(delimiter) if languages => print!(
+                "{}",
+                delimited::diff_stats_by_language(&diff_stats, delimiter)
+            ),
+            Format::Delimited(delimiter) => {
+                print!("{}", delimited::diff_stats(&diff_stats, delimiter))
+            }
+            Format::Table if languages => {
+                println!("{}", DiffStatsTable::by_language(diff_stats, dimensions))
+            }
+            Format::Table => println!("{}", DiffStatsTable::new(diff_stats, dimensions)),
+            Format::Markdown if languages => print!(
+                "{}",
+                DiffStatsTable::by_language(diff_stats, dimensions).markdown()
+            ),
+            Format::Markdown => {
+                print!("{}", DiffStatsTable::new(diff_stats, dimensions).markdown())
+            }
+        }
+    } else if matches.is_present("files") {
+        let file_churn = gd.file_churn()?;
+        match format {
+            Format::Json => json::print(&json::sorted(&file_churn)),
+            Format::Delimited(delimiter) => {
+                print!("{}", delimited::file_churn(&file_churn, delimiter))
+            }
+            Format::Table | Format::Markdown => {
+                println!("{}", FileChurnTable::new(file_churn, dimensions))
+            }
+        }
+    } else if matches.is_present("timeline") {
+        let period = match matches.value_of("timeline") {
+            Some("day") => Period::Day,
+            Some("week") => Period::Week,
+            Some("month") => Period::Month,
+            _ => Period::default(),
+        };
+        let timeline = gd.timeline(period)?;
+        match format {
+            Format::Json => json::print(&json::timeline(&timeline, period)),
+            Format::Delimited(delimiter) => {
+                print!("{}", delimited::timeline(&timeline, period, delimiter))
+            }
+            Format::Table | Format::Markdown => {
+                println!("{}", TimelineTable::new(timeline, period, dimensions))
+            }
+        }
+    } else if matches.is_present("per-tag") {
+        let snapshots = gd.snapshots()?;
+        match format {
+            Format::Json => json::print(&snapshots),
+            Format::Delimited(delimiter) => {
+                print!("{}", delimited::snapshots(&snapshots, delimiter))
+            }
+            Format::Table | Format::Markdown => {
+                println!("{}", SnapshotsTable::new(snapshots, dimensions))
+            }
+        }
     } else {
         let final_contribs = gd.final_contributions()?;
-        println!(
-            "{}",
-            FinalContributionsTable::new(final_contribs, dimensions)
-        );
+        match format {
+            Format::Json => json::print(&final_contribs),
+            Format::Delimited(delimiter) => print!(
+                "{}",
+                delimited::final_contributions(&final_contribs, delimiter)
+            ),
+            Format::Table => println!(
+                "{}",
+                FinalContributionsTable::new(final_contribs, dimensions)
+            ),
+            Format::Markdown => print!(
+                "{}",
+                FinalContributionsTable::new(final_contribs, dimensions).markdown()
+            ),
+        }
     }
     Ok(())
 }
 
-fn list(matches: &ArgMatches, gd: &GitDetective) -> Result<(), Error> {
+fn report(matches: &ArgMatches, gd: &GitDetective) -> Result<(), Error> {
+    // Reports are named after the directory the repository is in
+    let name = env::current_dir()
+        .ok()
+        .and_then(|dir| {
+            dir.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .unwrap_or_default();
+    let report = Report::new(gd, name)?;
+    // Safe to unwrap, html is required
+    let path = PathBuf::from(matches.value_of("html").unwrap());
+    fs::write(&path, report.to_string()).map_err(|err| Error::IOError(err, path))
+}
+
+fn list(matches: &ArgMatches, gd: &GitDetective, format: Format) -> Result<(), Error> {
     let dimensions = term_size::dimensions();
     if matches.is_present("commits") {
         let commits: Vec<_> = gd.commits()?.collect();
-        println!("{}", CommitsTable::new(commits, dimensions));
+        let records: Vec<_> = commits.iter().map(CommitRecord::from).collect();
+        match format {
+            Format::Json => json::print(&records),
+            Format::Delimited(delimiter) => {
+                print!("{}", delimited::commits(&records, delimiter))
+            }
+            Format::Table => println!("{}", CommitsTable::new(commits, dimensions)),
+            Format::Markdown => print!(
+                "{}",
+                CommitsTable::new(commits, dimensions).markdown(gd.commit_counts()?)
+            ),
+        }
     } else if matches.is_present("tags") {
         let tags = gd.tags()?;
-        println!("{}", TagsTable::new(tags, dimensions));
+        let records: Vec<_> = tags.iter().map(TagRecord::from).collect();
+        match format {
+            Format::Json => json::print(&records),
+            Format::Delimited(delimiter) => print!("{}", delimited::tags(&records, delimiter)),
+            Format::Table => println!("{}", TagsTable::new(tags, dimensions)),
+            Format::Markdown => print!("{}", TagsTable::new(tags, dimensions).markdown()),
+        }
     } else if matches.is_present("branches") {
         let branches = gd.branches()?;
-        for branch in branches {
-            if let Ok(name) = branch.name() {
-                println!("{}", name);
+        match format {
+            Format::Json => json::print(&json::branches(branches)),
+            Format::Delimited(delimiter) => {
+                print!("{}", delimited::branches(branches, delimiter))
+            }
+            Format::Table | Format::Markdown => {
+                for branch in branches {
+                    if let Ok(name) = branch.name() {
+                        println!("{}", name);
+                    }
+                }
             }
         }
     } else {
         let contributors = gd.contributors()?;
-        for contributor in contributors {
-            println!("{}", contributor);
+        match format {
+            Format::Json => json::print(&json::contributors(&contributors)),
+            Format::Delimited(delimiter) => {
+                print!("{}", delimited::contributors(&contributors, delimiter))
+            }
+            Format::Table | Format::Markdown => {
+                for contributor in contributors {
+                    println!("{}", contributor);
+                }
+            }
         }
     }
     Ok(())
@@ -0,0 +1,434 @@
+//! Self-contained HTML report for `gd report --html`
+//!
+//! Styles and charts are inlined so the file can be opened anywhere without installing anything
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use git_detective::{
+    Activity, CommitRecord, Contributor, DiffStats, Error, GitDetective, Period, ProjectStats,
+};
+
+const CHART_WIDTH: usize = 800;
+const CHART_HEIGHT: usize = 200;
+const CHART_MARGIN: usize = 40;
+const COLORS: [&str; 8] = [
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#9c755f",
+];
+const STYLE: &str = "body{font-family:sans-serif;margin:2em auto;max-width:60em;color:#222}\
+table{border-collapse:collapse;margin-bottom:1em}\
+th,td{border:1px solid #ccc;padding:0.2em 0.6em;text-align:right}\
+th:first-child,td:first-child{text-align:left}\
+tr:nth-child(even){background:#f4f4f4}\
+.legend span{display:inline-block;width:1em;height:1em;margin:0 0.3em 0 1em;vertical-align:middle}";
+
+pub struct Report {
+    name: String,
+    generated: DateTime<Utc>,
+    final_contribs: ProjectStats,
+    diff_stats: HashMap<Contributor, DiffStats>,
+    timeline: BTreeMap<NaiveDate, HashMap<Contributor, Activity>>,
+    // Final contributions of every source file
+    ownership: BTreeMap<PathBuf, ProjectStats>,
+    commits: Vec<CommitRecord>,
+}
+
+impl Report {
+    pub fn new(gd: &GitDetective, name: String) -> Result<Self, Error> {
+        // Every file is blamed once for both final contributions and ownership
+        let mut final_contribs = ProjectStats::default();
+        let mut ownership = BTreeMap::new();
+        for (path, file_stats) in gd.final_contributions_by_file()? {
+            // Files counted in a separate category aren't source code anyone owns
+            if file_stats.total_lines() > 0 {
+                ownership.insert(path, file_stats.clone());
+            }
+            final_contribs += file_stats;
+        }
+        Ok(Self {
+            name,
+            generated: Utc::now(),
+            final_contribs,
+            diff_stats: gd.diff_stats()?,
+            timeline: gd.timeline(Period::Week)?,
+            ownership,
+            commits: gd.commits()?.map(|commit| (&commit).into()).collect(),
+        })
+    }
+
+    // Every contributor sorted by name, which also decides their color
+    fn contributors(&self) -> Vec<&Contributor> {
+        let mut contributors: Vec<_> = self
+            .final_contribs
+            .contributors()
+            .chain(self.diff_stats.keys())
+            .chain(self.timeline.values().flat_map(|activity| activity.keys()))
+            .collect();
+        contributors.sort_unstable();
+        contributors.dedup();
+        contributors
+    }
+
+    fn final_contributions(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<h2>Final Contributions</h2>")?;
+        let total_lines = self.final_contribs.total_lines();
+        let mut contributions: Vec<_> = self.final_contribs.iter().collect();
+        contributions.sort_unstable_by_key(|(author, _)| *author);
+        writeln!(f, "<table>")?;
+        writeln!(f, "<tr><th>Contributor</th><th>Lines</th><th>Code</th><th>Comments</th><th>Blanks</th><th>Share</th></tr>")?;
+        for (author, _) in contributions.iter() {
+            let total = self
+                .final_contribs
+                .total_contribs_by_name(author.name())
+                .unwrap_or_default();
+            writeln!(
+                f,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(author.name()),
+                total.lines,
+                total.code,
+                total.comments,
+                total.blanks,
+                percent(total.lines, total_lines),
+            )?;
+        }
+        writeln!(f, "</table>")?;
+
+        writeln!(f, "<h3>By Language</h3>")?;
+        writeln!(f, "<table>")?;
+        writeln!(f, "<tr><th>Contributor</th><th>Language</th><th>Lines</th><th>Code</th><th>Comments</th><th>Blanks</th></tr>")?;
+        for (author, lang_map) in contributions {
+            let mut lang_stats: Vec<_> = lang_map.iter().collect();
+            lang_stats.sort_unstable_by_key(|(lang, _)| *lang);
+            for (lang, stats) in lang_stats {
+                writeln!(
+                    f,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape(author.name()),
+                    escape(lang),
+                    stats.lines,
+                    stats.code,
+                    stats.comments,
+                    stats.blanks,
+                )?;
+            }
+        }
+        writeln!(f, "</table>")
+    }
+
+    fn diff_stats(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<h2>Changes</h2>")?;
+        let mut commits: HashMap<&str, usize> = HashMap::new();
+        for (contributor, activity) in self.timeline.values().flatten() {
+            *commits.entry(contributor.name()).or_default() += activity.commits;
+        }
+        let mut authored_diffs: Vec<_> = self.diff_stats.iter().collect();
+        authored_diffs.sort_unstable_by_key(|(author, _)| *author);
+        writeln!(f, "<table>")?;
+        writeln!(f, "<tr><th>Contributor</th><th>Commits</th><th>Insertions</th><th>Deletions</th><th>Code Inserted</th><th>Comments Inserted</th><th>Binary Files</th></tr>")?;
+        for (author, stats) in authored_diffs {
+            writeln!(
+                f,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(author.name()),
+                commits.get(author.name()).copied().unwrap_or_default(),
+                stats.insertions,
+                stats.deletions,
+                stats.inserted.code,
+                stats.inserted.comments,
+                stats.binary.added + stats.binary.modified,
+            )?;
+        }
+        writeln!(f, "</table>")
+    }
+
+    fn timeline(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<h2>Timeline</h2>")?;
+        let (first, last) = match (self.timeline.keys().next(), self.timeline.keys().last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return writeln!(f, "<p>No commits</p>"),
+        };
+        // Weeks without any commits are kept, gaps are what the chart is for
+        let mut weeks = vec![first];
+        let mut week = first;
+        while week < last {
+            week += Duration::weeks(1);
+            weeks.push(week);
+        }
+        let contributors = self.contributors();
+        write!(f, "<p class=\"legend\">")?;
+        for (index, contributor) in contributors.iter().enumerate() {
+            write!(
+                f,
+                "<span style=\"background:{}\"></span>{}",
+                COLORS[index % COLORS.len()],
+                escape(contributor.name())
+            )?;
+        }
+        writeln!(f, "</p>")?;
+        writeln!(f, "<h3>Commits per Week</h3>")?;
+        self.chart(f, &weeks, &contributors, |activity| activity.commits)?;
+        writeln!(f, "<h3>Insertions per Week</h3>")?;
+        self.chart(f, &weeks, &contributors, |activity| activity.insertions)
+    }
+
+    // Stacked bar chart with a bar per week and a segment per contributor
+    fn chart<F>(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        weeks: &[NaiveDate],
+        contributors: &[&Contributor],
+        value: F,
+    ) -> fmt::Result
+    where
+        F: Fn(&Activity) -> usize,
+    {
+        let totals: Vec<usize> = weeks
+            .iter()
+            .map(|week| match self.timeline.get(week) {
+                Some(activity) => activity.values().map(&value).sum(),
+                None => 0,
+            })
+            .collect();
+        let max = totals.iter().copied().max().unwrap_or_default().max(1);
+        let plot_width = CHART_WIDTH - 2 * CHART_MARGIN;
+        let plot_height = CHART_HEIGHT - 2 * CHART_MARGIN;
+        let bar_width = plot_width as f64 / weeks.len() as f64;
+        writeln!(
+            f,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">",
+            width = CHART_WIDTH,
+            height = CHART_HEIGHT,
+        )?;
+        writeln!(
+            f,
+            "<line x1=\"{m}\" y1=\"{y}\" x2=\"{x}\" y2=\"{y}\" stroke=\"#222\"/>",
+            m = CHART_MARGIN,
+            x = CHART_WIDTH - CHART_MARGIN,
+            y = CHART_HEIGHT - CHART_MARGIN,
+        )?;
+        writeln!(
+            f,
+            "<text x=\"{}\" y=\"{}\" font-size=\"12\" text-anchor=\"end\">{}</text>",
+            CHART_MARGIN - 4,
+            CHART_MARGIN + 4,
+            max
+        )?;
+        for (index, week) in weeks.iter().enumerate() {
+            let x = CHART_MARGIN as f64 + index as f64 * bar_width;
+            let mut y = (CHART_HEIGHT - CHART_MARGIN) as f64;
+            for (color, contributor) in contributors.iter().enumerate() {
+                let amount = self
+                    .timeline
+                    .get(week)
+                    .and_then(|activity| activity.get(contributor.name()))
+                    .map(&value)
+                    .unwrap_or_default();
+                if amount == 0 {
+                    continue;
+                }
+                let height = amount as f64 / max as f64 * plot_height as f64;
+                y -= height;
+                writeln!(
+                    f,
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"><title>{} {}: {}</title></rect>",
+                    x + 1.0,
+                    y,
+                    (bar_width - 2.0).max(1.0),
+                    height,
+                    COLORS[color % COLORS.len()],
+                    escape(contributor.name()),
+                    Period::Week.label(*week),
+                    amount,
+                )?;
+            }
+        }
+        // Label the first and last week
+        let mut labels = vec![(CHART_MARGIN, "start", weeks[0])];
+        if weeks.len() > 1 {
+            labels.push((CHART_WIDTH - CHART_MARGIN, "end", weeks[weeks.len() - 1]));
+        }
+        for (x, anchor, week) in labels {
+            writeln!(
+                f,
+                "<text x=\"{}\" y=\"{}\" font-size=\"12\" text-anchor=\"{}\">{}</text>",
+                x,
+                CHART_HEIGHT - CHART_MARGIN + 16,
+                anchor,
+                Period::Week.label(week),
+            )?;
+        }
+        writeln!(f, "</svg>")
+    }
+
+    fn ownership(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<h2>File Ownership</h2>")?;
+        writeln!(f, "<table>")?;
+        writeln!(f, "<tr><th>File</th><th>Language</th><th>Lines</th><th>Owner</th><th>Share</th><th>Contributors</th></tr>")?;
+        for (path, file_stats) in self.ownership.iter() {
+            let lines = file_stats.total_lines();
+            // A file has a single language
+            let lang = file_stats
+                .iter()
+                .flat_map(|(_, lang_map)| lang_map.keys())
+                .next()
+                .copied()
+                .unwrap_or_default();
+            let owned: Vec<_> = file_stats
+                .iter()
+                .map(|(author, lang_map)| {
+                    let lines: usize = lang_map.values().map(|stats| stats.lines).sum();
+                    (author, lines)
+                })
+                .collect();
+            // Most lines wins, ties go to the first name
+            let owner = owned.iter().max_by(|(author_lhs, lhs), (author_rhs, rhs)| {
+                lhs.cmp(rhs).then_with(|| author_rhs.cmp(author_lhs))
+            });
+            let (owner, owned_lines) = match owner {
+                Some((owner, lines)) => (owner.name(), *lines),
+                None => ("", 0),
+            };
+            writeln!(
+                f,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&path.to_string_lossy()),
+                escape(lang),
+                lines,
+                escape(owner),
+                percent(owned_lines, lines),
+                owned.len(),
+            )?;
+        }
+        writeln!(f, "</table>")
+    }
+
+    fn commits(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<h2>Commits</h2>")?;
+        writeln!(f, "<table>")?;
+        writeln!(
+            f,
+            "<tr><th>Commit</th><th>Date</th><th>Author</th><th>Summary</th></tr>"
+        )?;
+        for commit in self.commits.iter() {
+            let mut authors = vec![commit.author.name.as_str()];
+            authors.extend(commit.co_authors.iter().map(|author| author.name.as_str()));
+            writeln!(
+                f,
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td style=\"text-align:left\">{}</td></tr>",
+                &commit.id[..10],
+                commit.date.format("%Y-%m-%d %H:%M"),
+                escape(&authors.join(", ")),
+                escape(commit.summary.as_deref().unwrap_or_default()),
+            )?;
+        }
+        writeln!(f, "</table>")
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<!DOCTYPE html>")?;
+        writeln!(f, "<html lang=\"en\">")?;
+        writeln!(f, "<head>")?;
+        writeln!(f, "<meta charset=\"utf-8\">")?;
+        writeln!(f, "<title>{} - Git Detective</title>", escape(&self.name))?;
+        writeln!(f, "<style>{}</style>", STYLE)?;
+        writeln!(f, "</head>")?;
+        writeln!(f, "<body>")?;
+        writeln!(f, "<h1>{}</h1>", escape(&self.name))?;
+        write!(
+            f,
+            "<p>Generated {}",
+            self.generated.format("%Y-%m-%d %H:%M UTC")
+        )?;
+        if let Some(head) = self.commits.first() {
+            write!(f, " at commit <code>{}</code>", head.id)?;
+        }
+        writeln!(f, "</p>")?;
+        self.final_contributions(f)?;
+        self.diff_stats(f)?;
+        self.timeline(f)?;
+        self.ownership(f)?;
+        self.commits(f)?;
+        writeln!(f, "</body>")?;
+        writeln!(f, "</html>")
+    }
+}
+
+fn percent(part: usize, total: usize) -> String {
+    if total == 0 {
+        return String::from("0.0%");
+    }
+    format!("{:.1}%", part as f64 / total as f64 * 100.0)
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::remove_dir_all;
+
+    use git_detective::{Error, GitDetective};
+
+    use super::{escape, Report};
+
+    #[test]
+    fn escape_names() {
+        assert_eq!(escape("<b>Alice</b>"), "&lt;b&gt;Alice&lt;/b&gt;");
+        assert_eq!(escape("Smith & Sons"), "Smith &amp; Sons");
+        assert_eq!(escape("\"Bobby\" O'Neil"), "&quot;Bobby&quot; O&#39;Neil");
+    }
+
+    #[test]
+    fn self_contained() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("gd_report");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        std::fs::write(path.join("main.rs"), "// Entry point\nfn main() {}\n").unwrap();
+        let mut index = repository.index()?;
+        index.add_path(std::path::Path::new("main.rs"))?;
+        index.write()?;
+        let tree = repository.find_tree(index.write_tree()?)?;
+        let signature = git2::Signature::now("Eve \"M\" & Co", "eve@example.com")?;
+        repository.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Add <script>",
+            &tree,
+            &[],
+        )?;
+
+        let gd = GitDetective::open(&path)?;
+        let html = Report::new(&gd, String::from("gd_report"))?.to_string();
+        assert!(html.starts_with("<!DOCTYPE html>\n"));
+        assert!(html.trim_end().ends_with("</html>"));
+        assert!(html.contains("Eve &quot;M&quot; &amp; Co"));
+        assert!(html.contains("Add &lt;script&gt;"));
+        assert!(!html.contains("<script"));
+        // Nothing is loaded from anywhere else, the SVG namespace is only a name
+        let html = html.replace("xmlns=\"http://www.w3.org/2000/svg\"", "");
+        for external in ["http://", "https://", "src=", "href=", "@import", "url("].iter() {
+            assert!(!html.contains(external), "{} in report", external);
+        }
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
+use std::collections::HashMap;
 use std::fmt;
 
-use git_detective::Commit;
+use git_detective::{Commit, Contributor};
+
+use super::markdown;
 
 const WIDTH: usize = 96;
 const SHORT_ID_LEN: usize = 6;
@@ -11,6 +14,8 @@ pub struct CommitsTable<'commit> {
     separator_length: usize,
     id_length: usize,
     commits: Vec<Commit<'commit>>,
+    markdown: bool,
+    commit_counts: HashMap<Contributor, usize>,
 }
 
 impl<'commit> CommitsTable<'commit> {
@@ -28,9 +33,62 @@ impl<'commit> CommitsTable<'commit> {
             commits,
             id_length,
             separator_length: width,
+            markdown: false,
+            commit_counts: HashMap::new(),
+        }
+    }
+
+    // Totals are credited by the library, so aliases and co-authors are counted like every other view.
+    // A commit with co-authors is credited more than once, shares are of all credits rather than commits
+    pub fn markdown(self, commit_counts: HashMap<Contributor, usize>) -> Self {
+        Self {
+            markdown: true,
+            commit_counts,
+            ..self
         }
     }
 
+    // Every commit with its full summary, then commits and share of commits by contributor
+    fn fmt_markdown(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        markdown::header(
+            f,
+            &["ID", "Author", "Committer", "Date (UTC)", "Summary"],
+            5,
+        )?;
+        for commit in self.commits.iter() {
+            let id: String = commit
+                .id()
+                .to_string()
+                .chars()
+                .take(self.id_length)
+                .collect();
+            let long_date = commit.date().to_string();
+            let summary = commit
+                .summary()
+                .unwrap_or_else(|| Ok(String::new()))
+                .unwrap_or_default();
+            markdown::row(
+                f,
+                &[
+                    format!("`{}`", id),
+                    commit.author().name().unwrap_or_default(),
+                    commit.committer().name().unwrap_or_default(),
+                    long_date[..long_date.len() - 4].to_string(),
+                    summary,
+                ],
+            )?;
+        }
+
+        writeln!(f)?;
+        let mut commit_counts: Vec<_> = self
+            .commit_counts
+            .iter()
+            .map(|(contributor, commits)| (contributor.name(), *commits))
+            .collect();
+        commit_counts.sort_unstable();
+        markdown::totals(f, &["Contributor", "Commits", "Share"], &commit_counts)
+    }
+
     // Check to see if `SHORT_ID_LEN` is long enough for uniquness
     fn unique_short_ids(commits: &[Commit<'_>]) -> bool {
         for (index, commit) in commits.iter().enumerate() {
@@ -123,6 +181,9 @@ impl<'commit> fmt::Display for CommitsTable<'_> {
         if self.commits.is_empty() {
             return Ok(());
         }
+        if self.markdown {
+            return self.fmt_markdown(f);
+        }
         self.header(f)?;
         for commit in self.commits.iter() {
             self.row(f, &commit)?;
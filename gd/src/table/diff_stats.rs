@@ -1,18 +1,22 @@
 use std::collections::HashMap;
 use std::fmt;
 
-use git_detective::DiffStats;
+use git_detective::{Contributor, DiffStats, WhitespaceMode};
+
+use super::markdown;
 
 const WIDTH: usize = 60;
 const ITEMS: usize = 5;
 
 pub struct DiffStatsTable {
     separator_length: usize,
-    stats: HashMap<String, DiffStats>,
+    stats: HashMap<Contributor, DiffStats>,
+    by_language: bool,
+    markdown: bool,
 }
 
 impl DiffStatsTable {
-    pub fn new(stats: HashMap<String, DiffStats>, dimensions: Option<(usize, usize)>) -> Self {
+    pub fn new(stats: HashMap<Contributor, DiffStats>, dimensions: Option<(usize, usize)>) -> Self {
         let (mut width, _) = dimensions.unwrap_or((WIDTH, 0));
         if width > WIDTH {
             width = WIDTH;
@@ -20,6 +24,109 @@ impl DiffStatsTable {
         Self {
             stats,
             separator_length: width,
+            by_language: false,
+            markdown: false,
+        }
+    }
+
+    pub fn markdown(self) -> Self {
+        Self {
+            markdown: true,
+            ..self
+        }
+    }
+
+    // Totals and share of all insertions and deletions by contributor, by language shares are of the
+    // contributor's own changes
+    fn fmt_markdown(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut authored_diffs: Vec<_> = self.stats.iter().collect();
+        authored_diffs.sort_unstable_by_key(|(author, _)| *author);
+        let insertions: usize = self.stats.values().map(|stats| stats.insertions).sum();
+        let deletions: usize = self.stats.values().map(|stats| stats.deletions).sum();
+        markdown::header(
+            f,
+            &[
+                "Contributor",
+                "Insertions",
+                "Deletions",
+                "Share of Insertions",
+                "Share of Deletions",
+            ],
+            1,
+        )?;
+        for (author, diff_stats) in authored_diffs.iter() {
+            markdown::row(
+                f,
+                &changes(author.name(), diff_stats, (insertions, deletions)),
+            )?;
+        }
+        let total = DiffStats {
+            insertions,
+            deletions,
+            ..DiffStats::default()
+        };
+        markdown::row(f, &changes("**Total**", &total, (insertions, deletions)))?;
+
+        if self.by_language {
+            writeln!(f)?;
+            markdown::header(
+                f,
+                &[
+                    "Contributor",
+                    "Language",
+                    "Insertions",
+                    "Deletions",
+                    "Share of Insertions",
+                    "Share of Deletions",
+                ],
+                2,
+            )?;
+            for (author, diff_stats) in authored_diffs.iter() {
+                for (language, language_stats) in diff_stats.languages.iter() {
+                    let mut row = vec![author.name().to_string()];
+                    row.extend(changes(language, language_stats, lines(diff_stats)));
+                    markdown::row(f, &row)?;
+                }
+            }
+        }
+
+        let binary_diffs: Vec<_> = authored_diffs
+            .iter()
+            .filter(|(_, stats)| stats.binary.added + stats.binary.modified > 0)
+            .collect();
+        if !binary_diffs.is_empty() {
+            writeln!(f)?;
+            markdown::header(f, &["Contributor", "Binary Files", "Bytes"], 1)?;
+            for (author, diff_stats) in binary_diffs {
+                let binary = diff_stats.binary;
+                markdown::row(
+                    f,
+                    &[
+                        author.name().to_string(),
+                        (binary.added + binary.modified).to_string(),
+                        binary.bytes.to_string(),
+                    ],
+                )?;
+            }
+        }
+        let insensitive = self
+            .stats
+            .values()
+            .any(|stats| stats.whitespace_mode == WhitespaceMode::Insensitive);
+        if insensitive {
+            writeln!(f)?;
+            writeln!(f, "_Whitespace-only changes are ignored_")?;
+        }
+        Ok(())
+    }
+
+    pub fn by_language(
+        stats: HashMap<Contributor, DiffStats>,
+        dimensions: Option<(usize, usize)>,
+    ) -> Self {
+        Self {
+            by_language: true,
+            ..Self::new(stats, dimensions)
         }
     }
 
@@ -27,7 +134,7 @@ impl DiffStatsTable {
         2 * (self.separator_length / ITEMS)
     }
 
-    fn row(&self, f: &mut fmt::Formatter<'_>, name: &str, stats: &DiffStats) -> fmt::Result {
+    fn row(&self, f: &mut fmt::Formatter<'_>, name: &str, columns: (usize, usize)) -> fmt::Result {
         let author_width = self.author_width();
         // Truncate names that are too long
         let name = if name.len() > author_width {
@@ -41,21 +148,30 @@ impl DiffStatsTable {
             f,
             "{:^author_width$} {:>width$} {:>width$}",
             name,
-            stats.insertions,
-            stats.deletions,
+            columns.0,
+            columns.1,
             author_width = author_width,
             width = self.separator_length / ITEMS,
         )
     }
 
-    fn header(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fn author(&self, f: &mut fmt::Formatter<'_>, author: &str) -> fmt::Result {
+        writeln!(
+            f,
+            "{:^width$}",
+            format!("{}'s changes", author),
+            width = self.separator_length
+        )
+    }
+
+    fn header(&self, f: &mut fmt::Formatter<'_>, titles: [&str; 3]) -> fmt::Result {
         self.line_separator(f)?;
         writeln!(
             f,
             "{:^author_width$} {:>width$} {:>width$}",
-            "Contributor",
-            "Insertions",
-            "Deletions",
+            titles[0],
+            titles[1],
+            titles[2],
             author_width = self.author_width(),
             width = self.separator_length / ITEMS,
         )?;
@@ -67,21 +183,78 @@ impl DiffStatsTable {
     }
 }
 
+fn lines(stats: &DiffStats) -> (usize, usize) {
+    (stats.insertions, stats.deletions)
+}
+
+// Markdown cells for `stats` and their share of `totals` insertions and deletions
+fn changes(name: &str, stats: &DiffStats, totals: (usize, usize)) -> Vec<String> {
+    vec![
+        name.to_string(),
+        stats.insertions.to_string(),
+        stats.deletions.to_string(),
+        markdown::percent(stats.insertions, totals.0),
+        markdown::percent(stats.deletions, totals.1),
+    ]
+}
+
 impl fmt::Display for DiffStatsTable {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.stats.is_empty() {
             return Ok(());
         }
-        self.header(f)?;
+        if self.markdown {
+            return self.fmt_markdown(f);
+        }
         let mut authored_diffs: Vec<_> = self.stats.iter().collect();
         authored_diffs
             .as_mut_slice()
             .sort_unstable_by(|(author_lhs, _), (author_rhs, _)| author_lhs.cmp(author_rhs));
-        for (author, diff_stats) in authored_diffs.iter() {
-            self.row(f, &author, diff_stats)?;
+        if self.by_language {
+            for (author, diff_stats) in authored_diffs.iter() {
+                self.author(f, author.name())?;
+                self.header(f, ["Language", "Insertions", "Deletions"])?;
+                for (language, language_stats) in diff_stats.languages.iter() {
+                    self.row(f, language, lines(language_stats))?;
+                }
+                self.line_separator(f)?;
+                self.row(f, "Total", lines(diff_stats))?;
+                self.line_separator(f)?;
+                writeln!(f)?;
+            }
+        } else {
+            self.header(f, ["Contributor", "Insertions", "Deletions"])?;
+            for (author, diff_stats) in authored_diffs.iter() {
+                self.row(f, author.name(), lines(diff_stats))?;
+            }
+            self.line_separator(f)?;
+            writeln!(f)?;
+        }
+        // Binary files have no lines, so they're listed separately
+        let binary_diffs: Vec<_> = authored_diffs
+            .iter()
+            .filter(|(_, stats)| stats.binary.added + stats.binary.modified > 0)
+            .collect();
+        if !binary_diffs.is_empty() {
+            self.header(f, ["Contributor", "Binary Files", "Bytes"])?;
+            for (author, diff_stats) in binary_diffs {
+                let binary = diff_stats.binary;
+                self.row(
+                    f,
+                    author.name(),
+                    (binary.added + binary.modified, binary.bytes),
+                )?;
+            }
+            self.line_separator(f)?;
+            writeln!(f)?;
+        }
+        let insensitive = self
+            .stats
+            .values()
+            .any(|stats| stats.whitespace_mode == WhitespaceMode::Insensitive);
+        if insensitive {
+            writeln!(f, "Whitespace-only changes are ignored")?;
         }
-        self.line_separator(f)?;
-        writeln!(f)?;
         Ok(())
     }
 }
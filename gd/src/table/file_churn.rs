@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use git_detective::FileChurn;
+
+const WIDTH: usize = 84;
+const ITEMS: usize = 7;
+
+pub struct FileChurnTable {
+    separator_length: usize,
+    churn: HashMap<PathBuf, FileChurn>,
+}
+
+impl FileChurnTable {
+    pub fn new(churn: HashMap<PathBuf, FileChurn>, dimensions: Option<(usize, usize)>) -> Self {
+        let (mut width, _) = dimensions.unwrap_or((WIDTH, 0));
+        if width > WIDTH {
+            width = WIDTH;
+        }
+        Self {
+            churn,
+            separator_length: width,
+        }
+    }
+
+    // The path takes up whatever the four number columns leave
+    fn path_width(&self) -> usize {
+        self.separator_length - 4 * (self.separator_length / ITEMS + 1)
+    }
+
+    fn row(&self, f: &mut fmt::Formatter<'_>, path: &str, churn: &FileChurn) -> fmt::Result {
+        let path_width = self.path_width();
+        // Truncate the start of paths that are too long, the file name matters most
+        let path = if path.chars().count() > path_width {
+            let skip = path.chars().count() - (path_width - 3);
+            let mut truncated = String::from("...");
+            truncated.extend(path.chars().skip(skip));
+            truncated
+        } else {
+            path.to_string()
+        };
+        writeln!(
+            f,
+            "{:<path_width$} {:>width$} {:>width$} {:>width$} {:>width$}",
+            path,
+            churn.commits,
+            churn.insertions,
+            churn.deletions,
+            churn.authors(),
+            path_width = path_width,
+            width = self.separator_length / ITEMS,
+        )
+    }
+
+    fn header(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.line_separator(f)?;
+        writeln!(
+            f,
+            "{:<path_width$} {:>width$} {:>width$} {:>width$} {:>width$}",
+            "File",
+            "Commits",
+            "Insertions",
+            "Deletions",
+            "Authors",
+            path_width = self.path_width(),
+            width = self.separator_length / ITEMS,
+        )?;
+        self.line_separator(f)
+    }
+
+    fn line_separator(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", "-".repeat(self.separator_length))
+    }
+}
+
+impl fmt::Display for FileChurnTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.churn.is_empty() {
+            return Ok(());
+        }
+        self.header(f)?;
+        let mut churn: Vec<_> = self.churn.iter().collect();
+        // Most changed files first
+        churn
+            .as_mut_slice()
+            .sort_unstable_by(|(path_lhs, lhs), (path_rhs, rhs)| {
+                rhs.commits
+                    .cmp(&lhs.commits)
+                    .then_with(|| path_lhs.cmp(path_rhs))
+            });
+        for (path, file_churn) in churn {
+            self.row(f, &path.to_string_lossy(), file_churn)?;
+        }
+        self.line_separator(f)?;
+        writeln!(f)
+    }
+}
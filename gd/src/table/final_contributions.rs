@@ -1,6 +1,8 @@
 use std::fmt;
 
-use git_detective::{ProjectStats, Stats};
+use git_detective::{FileCategory, ProjectStats, Stats, WhitespaceMode};
+
+use super::markdown;
 
 const WIDTH: usize = 72;
 const ITEMS: usize = 6;
@@ -8,6 +10,7 @@ const ITEMS: usize = 6;
 pub struct FinalContributionsTable {
     separator_length: usize,
     stats: ProjectStats,
+    markdown: bool,
 }
 
 impl FinalContributionsTable {
@@ -19,7 +22,171 @@ impl FinalContributionsTable {
         Self {
             stats,
             separator_length: width,
+            markdown: false,
+        }
+    }
+
+    pub fn markdown(self) -> Self {
+        Self {
+            markdown: true,
+            ..self
+        }
+    }
+
+    // Source code first, then every category that was counted separately
+    fn fmt_markdown(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut written = false;
+        for (category, stats) in self.sections() {
+            if written {
+                writeln!(f)?;
+            }
+            if let Some(category) = category {
+                writeln!(f, "**{:?} files**", category)?;
+                writeln!(f)?;
+            }
+            self.markdown_contributions(f, stats)?;
+            written = true;
+        }
+        if self.stats.whitespace_mode() == WhitespaceMode::Insensitive {
+            writeln!(f)?;
+            writeln!(f, "_Whitespace-only changes are ignored_")?;
         }
+        Ok(())
+    }
+
+    // Totals and share of all lines by contributor, then each contributor's lines by language
+    fn markdown_contributions(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        stats: &ProjectStats,
+    ) -> fmt::Result {
+        let mut contributions: Vec<_> = stats.iter().collect();
+        contributions.sort_unstable_by_key(|(author, _)| *author);
+        let totals: Vec<_> = contributions
+            .iter()
+            .map(|(author, lang_map)| {
+                (
+                    *author,
+                    lang_map
+                        .values()
+                        .copied()
+                        .fold(Stats::default(), |total, stats| total + stats),
+                )
+            })
+            .collect();
+        let total_lines = stats.total_lines();
+        markdown::header(
+            f,
+            &[
+                "Contributor",
+                "Lines",
+                "Code",
+                "Comments",
+                "Blanks",
+                "Share",
+            ],
+            1,
+        )?;
+        let mut project_total = Stats::default();
+        for (author, total) in totals.iter() {
+            project_total += *total;
+            markdown::row(
+                f,
+                &[
+                    author.name().to_string(),
+                    total.lines.to_string(),
+                    total.code.to_string(),
+                    total.comments.to_string(),
+                    total.blanks.to_string(),
+                    markdown::percent(total.lines, total_lines),
+                ],
+            )?;
+        }
+        markdown::row(
+            f,
+            &[
+                String::from("**Total**"),
+                project_total.lines.to_string(),
+                project_total.code.to_string(),
+                project_total.comments.to_string(),
+                project_total.blanks.to_string(),
+                markdown::percent(project_total.lines, total_lines),
+            ],
+        )?;
+        writeln!(f)?;
+
+        markdown::header(
+            f,
+            &[
+                "Contributor",
+                "Language",
+                "Lines",
+                "Code",
+                "Comments",
+                "Blanks",
+                "Share",
+            ],
+            2,
+        )?;
+        for ((author, lang_map), (_, total)) in contributions.iter().zip(totals.iter()) {
+            let mut lang_stats: Vec<_> = lang_map.iter().collect();
+            lang_stats.sort_unstable_by_key(|(lang, _)| *lang);
+            // Share of the contributor's own lines
+            for (lang, stats) in lang_stats {
+                markdown::row(
+                    f,
+                    &[
+                        author.name().to_string(),
+                        lang.to_string(),
+                        stats.lines.to_string(),
+                        stats.code.to_string(),
+                        stats.comments.to_string(),
+                        stats.blanks.to_string(),
+                        markdown::percent(stats.lines, total.lines),
+                    ],
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    // Source code, followed by every separately counted category sorted by category, empty ones are skipped
+    fn sections(&self) -> Vec<(Option<FileCategory>, &ProjectStats)> {
+        let mut categories: Vec<_> = self
+            .stats
+            .categories()
+            .map(|(category, stats)| (Some(*category), stats))
+            .collect();
+        categories.sort_unstable_by_key(|(category, _)| *category);
+        let mut sections = vec![(None, &self.stats)];
+        sections.extend(categories);
+        sections.retain(|(_, stats)| stats.iter().next().is_some());
+        sections
+    }
+
+    // A table of each contributor's lines by language
+    fn contributions(&self, f: &mut fmt::Formatter<'_>, stats: &ProjectStats) -> fmt::Result {
+        for (author, lang_map) in stats.iter() {
+            self.author(f, author.name())?;
+            self.header(f)?;
+            let mut total = Stats::default();
+
+            let mut lang_stats: Vec<_> = lang_map.iter().collect();
+            // Sort by
+            lang_stats
+                .as_mut_slice()
+                .sort_unstable_by(|(lang_lhs, _), (lang_rhs, _)| lang_lhs.cmp(lang_rhs));
+
+            for (lang, stats) in lang_stats {
+                total += *stats;
+                self.row(f, lang, stats)?;
+            }
+            self.line_separator(f)?;
+            self.row(f, "Total", &total)?;
+            self.line_separator(f)?;
+            writeln!(f)?;
+        }
+        Ok(())
     }
 
     fn row(&self, f: &mut fmt::Formatter<'_>, row_name: &str, stats: &Stats) -> fmt::Result {
@@ -69,25 +236,23 @@ impl fmt::Display for FinalContributionsTable {
         if self.stats.is_empty() {
             return Ok(());
         }
-        for (author, lang_map) in self.stats.iter() {
-            self.author(f, &author)?;
-            self.header(f)?;
-            let mut total = Stats::default();
-
-            let mut lang_stats: Vec<_> = lang_map.iter().collect();
-            // Sort by
-            lang_stats
-                .as_mut_slice()
-                .sort_unstable_by(|(lang_lhs, _), (lang_rhs, _)| lang_lhs.cmp(lang_rhs));
-
-            for (lang, stats) in lang_stats {
-                total += *stats;
-                self.row(f, lang, stats)?;
+        if self.markdown {
+            return self.fmt_markdown(f);
+        }
+        for (category, stats) in self.sections() {
+            if let Some(category) = category {
+                writeln!(
+                    f,
+                    "{:^width$}",
+                    format!("{:?} files", category),
+                    width = self.separator_length
+                )?;
+                writeln!(f)?;
             }
-            self.line_separator(f)?;
-            self.row(f, "Total", &total)?;
-            self.line_separator(f)?;
-            writeln!(f)?;
+            self.contributions(f, stats)?;
+        }
+        if self.stats.whitespace_mode() == WhitespaceMode::Insensitive {
+            writeln!(f, "Whitespace-only changes are ignored")?;
         }
         Ok(())
     }
@@ -0,0 +1,105 @@
+//! GitHub-flavoured Markdown tables for `--format markdown`
+
+use std::fmt;
+
+// Columns from `numeric` on are right aligned
+pub fn header(f: &mut fmt::Formatter<'_>, titles: &[&str], numeric: usize) -> fmt::Result {
+    row(f, titles)?;
+    let alignments: Vec<_> = (0..titles.len())
+        .map(|index| if index < numeric { "---" } else { "---:" })
+        .collect();
+    writeln!(f, "| {} |", alignments.join(" | "))
+}
+
+pub fn row<S: AsRef<str>>(f: &mut fmt::Formatter<'_>, cells: &[S]) -> fmt::Result {
+    let cells: Vec<_> = cells.iter().map(|cell| escape(cell.as_ref())).collect();
+    writeln!(f, "| {} |", cells.join(" | "))
+}
+
+// Every count with its share of all counts, followed by a **Total** row that adds them up
+pub fn totals<S: AsRef<str>>(
+    f: &mut fmt::Formatter<'_>,
+    titles: &[&str; 3],
+    counts: &[(S, usize)],
+) -> fmt::Result {
+    let total = counts.iter().map(|(_, count)| count).sum();
+    header(f, titles, 1)?;
+    for (name, count) in counts {
+        row(
+            f,
+            &[name.as_ref(), &count.to_string(), &percent(*count, total)],
+        )?;
+    }
+    row(
+        f,
+        &["**Total**", &total.to_string(), &percent(total, total)],
+    )
+}
+
+pub fn percent(part: usize, total: usize) -> String {
+    if total == 0 {
+        return String::from("0.0%");
+    }
+    format!("{:.1}%", part as f64 / total as f64 * 100.0)
+}
+
+// Pipes would end the cell and line breaks the row
+fn escape(cell: &str) -> String {
+    cell.replace('|', "\\|").replace(&['\r', '\n'][..], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+
+    use super::{escape, percent, row, totals};
+
+    // Render a Markdown writer to a string
+    struct Render<F>(F);
+
+    impl<F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result> fmt::Display for Render<F> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            (self.0)(f)
+        }
+    }
+
+    #[test]
+    fn escape_pipes() {
+        assert_eq!(escape("a|b"), "a\\|b");
+        let rendered =
+            Render(|f: &mut fmt::Formatter<'_>| row(f, &["Fix a || b", "1"])).to_string();
+        assert_eq!(rendered, "| Fix a \\|\\| b | 1 |\n");
+    }
+
+    #[test]
+    fn escape_line_breaks() {
+        assert_eq!(escape("first\nsecond"), "first second");
+        assert_eq!(escape("first\r\nsecond"), "first  second");
+    }
+
+    #[test]
+    fn percentages() {
+        assert_eq!(percent(1, 3), "33.3%");
+        assert_eq!(percent(2, 3), "66.7%");
+        assert_eq!(percent(0, 0), "0.0%");
+    }
+
+    #[test]
+    fn total_and_share_rows() {
+        // Co-authors are credited with the same commits, so shares are of all credits
+        let counts = [("Alice", 2), ("Bob", 2), ("Carol", 1)];
+        let rendered = Render(|f: &mut fmt::Formatter<'_>| {
+            totals(f, &["Contributor", "Commits", "Share"], &counts)
+        })
+        .to_string();
+        assert_eq!(
+            rendered,
+            "| Contributor | Commits | Share |\n\
+             | --- | ---: | ---: |\n\
+             | Alice | 2 | 40.0% |\n\
+             | Bob | 2 | 40.0% |\n\
+             | Carol | 1 | 20.0% |\n\
+             | **Total** | 5 | 100.0% |\n"
+        );
+    }
+}
@@ -1,9 +1,20 @@
+pub(crate) mod markdown;
+
 pub(crate) mod final_contributions;
 pub use final_contributions::FinalContributionsTable;
 
 pub(crate) mod diff_stats;
 pub use diff_stats::DiffStatsTable;
 
+pub(crate) mod file_churn;
+pub use file_churn::FileChurnTable;
+
+pub(crate) mod timeline;
+pub use timeline::TimelineTable;
+
+pub(crate) mod snapshots;
+pub use snapshots::SnapshotsTable;
+
 pub(crate) mod commits;
 pub use commits::CommitsTable;
 
@@ -0,0 +1,109 @@
+use std::fmt;
+
+use git_detective::{Snapshot, StatsDelta};
+
+const WIDTH: usize = 72;
+const ITEMS: usize = 7;
+
+pub struct SnapshotsTable {
+    separator_length: usize,
+    snapshots: Vec<Snapshot>,
+}
+
+impl SnapshotsTable {
+    pub fn new(snapshots: Vec<Snapshot>, dimensions: Option<(usize, usize)>) -> Self {
+        let (mut width, _) = dimensions.unwrap_or((WIDTH, 0));
+        if width > WIDTH {
+            width = WIDTH;
+        }
+        Self {
+            snapshots,
+            separator_length: width,
+        }
+    }
+
+    fn author_width(&self) -> usize {
+        2 * (self.separator_length / ITEMS)
+    }
+
+    fn row(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        name: &str,
+        lines: usize,
+        code: usize,
+        delta: &StatsDelta,
+    ) -> fmt::Result {
+        let author_width = self.author_width();
+        // Truncate names that are too long
+        let name = if name.chars().count() > author_width {
+            let mut name: String = name.chars().take(author_width - 3).collect();
+            name.push_str("...");
+            name
+        } else {
+            name.to_string()
+        };
+        writeln!(
+            f,
+            "{:^author_width$} {:>width$} {:>width$} {:>width$} {:>width$}",
+            name,
+            lines,
+            code,
+            format!("{:+}", delta.lines),
+            format!("{:+}", delta.code),
+            author_width = author_width,
+            width = self.separator_length / ITEMS,
+        )
+    }
+
+    fn snapshot(&self, f: &mut fmt::Formatter<'_>, snapshot: &Snapshot) -> fmt::Result {
+        let long_date = snapshot.date.to_string();
+        writeln!(
+            f,
+            "{:^width$}",
+            format!("{} ({})", snapshot.revision, &long_date[..10]),
+            width = self.separator_length
+        )
+    }
+
+    fn header(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.line_separator(f)?;
+        writeln!(
+            f,
+            "{:^author_width$} {:>width$} {:>width$} {:>width$} {:>width$}",
+            "Contributor",
+            "Lines",
+            "Code",
+            "Δ Lines",
+            "Δ Code",
+            author_width = self.author_width(),
+            width = self.separator_length / ITEMS,
+        )?;
+        self.line_separator(f)
+    }
+
+    fn line_separator(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", "-".repeat(self.separator_length))
+    }
+}
+
+impl fmt::Display for SnapshotsTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for snapshot in self.snapshots.iter() {
+            self.snapshot(f, snapshot)?;
+            self.header(f)?;
+            let mut deltas: Vec<_> = snapshot.delta.iter().collect();
+            deltas.sort_unstable_by_key(|(author, _)| *author);
+            for (author, delta) in deltas {
+                let total = snapshot
+                    .stats
+                    .total_contribs_by_name(author.name())
+                    .unwrap_or_default();
+                self.row(f, author.name(), total.lines, total.code, delta)?;
+            }
+            self.line_separator(f)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -2,12 +2,15 @@ use std::fmt;
 
 use git_detective::Tag;
 
+use super::markdown;
+
 const WIDTH: usize = 96;
 const ITEMS: usize = 3;
 
 pub struct TagsTable<'tag> {
     separator_length: usize,
     tags: Vec<Tag<'tag>>,
+    markdown: bool,
 }
 
 impl<'tag> TagsTable<'tag> {
@@ -19,10 +22,18 @@ impl<'tag> TagsTable<'tag> {
         Self {
             tags,
             separator_length: width,
+            markdown: false,
         }
     }
 
-    fn row(&self, f: &mut fmt::Formatter<'_>, tag: &Tag<'_>) -> fmt::Result {
+    pub fn markdown(self) -> Self {
+        Self {
+            markdown: true,
+            ..self
+        }
+    }
+
+    fn cells(tag: &Tag<'_>) -> [String; 3] {
         let (tagger, date) = match tag.tagger() {
             Some(tagger) => {
                 let long_date = tagger.date().to_string();
@@ -32,7 +43,19 @@ impl<'tag> TagsTable<'tag> {
             }
             None => (String::new(), String::new()),
         };
-        let name = tag.name().unwrap_or_default();
+        [tag.name().unwrap_or_default(), tagger, date]
+    }
+
+    fn fmt_markdown(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        markdown::header(f, &["Name", "Tagger", "Date (UTC)"], 3)?;
+        for tag in self.tags.iter() {
+            markdown::row(f, &Self::cells(tag))?;
+        }
+        Ok(())
+    }
+
+    fn row(&self, f: &mut fmt::Formatter<'_>, tag: &Tag<'_>) -> fmt::Result {
+        let [name, tagger, date] = Self::cells(tag);
         writeln!(
             f,
             "{:<width$} {:^width$} {:^width$}",
@@ -67,6 +90,9 @@ impl<'tag> fmt::Display for TagsTable<'_> {
         if self.tags.is_empty() {
             return Ok(());
         }
+        if self.markdown {
+            return self.fmt_markdown(f);
+        }
         self.header(f)?;
         for tag in self.tags.iter() {
             self.row(f, &tag)?;
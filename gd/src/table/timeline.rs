@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use chrono::NaiveDate;
+use git_detective::{Activity, Contributor, Period};
+
+const WIDTH: usize = 84;
+const ITEMS: usize = 7;
+
+pub struct TimelineTable {
+    separator_length: usize,
+    period: Period,
+    timeline: BTreeMap<NaiveDate, HashMap<Contributor, Activity>>,
+}
+
+impl TimelineTable {
+    pub fn new(
+        timeline: BTreeMap<NaiveDate, HashMap<Contributor, Activity>>,
+        period: Period,
+        dimensions: Option<(usize, usize)>,
+    ) -> Self {
+        let (mut width, _) = dimensions.unwrap_or((WIDTH, 0));
+        if width > WIDTH {
+            width = WIDTH;
+        }
+        Self {
+            timeline,
+            period,
+            separator_length: width,
+        }
+    }
+
+    // The contributor takes up whatever the period and three number columns leave
+    fn author_width(&self) -> usize {
+        self.separator_length - 4 * (self.separator_length / ITEMS + 1)
+    }
+
+    fn row(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        period: &str,
+        name: &str,
+        activity: &Activity,
+    ) -> fmt::Result {
+        let author_width = self.author_width();
+        // Truncate names that are too long
+        let name = if name.chars().count() > author_width {
+            let mut name: String = name.chars().take(author_width - 3).collect();
+            name.push_str("...");
+            name
+        } else {
+            name.to_string()
+        };
+        writeln!(
+            f,
+            "{:<width$} {:<author_width$} {:>width$} {:>width$} {:>width$}",
+            period,
+            name,
+            activity.commits,
+            activity.insertions,
+            activity.deletions,
+            author_width = author_width,
+            width = self.separator_length / ITEMS,
+        )
+    }
+
+    fn header(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.line_separator(f)?;
+        writeln!(
+            f,
+            "{:<width$} {:<author_width$} {:>width$} {:>width$} {:>width$}",
+            "Period",
+            "Contributor",
+            "Commits",
+            "Insertions",
+            "Deletions",
+            author_width = self.author_width(),
+            width = self.separator_length / ITEMS,
+        )?;
+        self.line_separator(f)
+    }
+
+    fn line_separator(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", "-".repeat(self.separator_length))
+    }
+}
+
+impl fmt::Display for TimelineTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.timeline.is_empty() {
+            return Ok(());
+        }
+        self.header(f)?;
+        for (start, contributors) in self.timeline.iter() {
+            let mut contributors: Vec<_> = contributors.iter().collect();
+            contributors.sort_unstable_by_key(|(author, _)| *author);
+            // Only label the first row of each period
+            let label = self.period.label(*start);
+            for (index, (author, activity)) in contributors.into_iter().enumerate() {
+                let period = if index == 0 { label.as_str() } else { "" };
+                self.row(f, period, author.name(), activity)?;
+            }
+        }
+        self.line_separator(f)?;
+        writeln!(f)
+    }
+}
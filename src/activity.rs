@@ -0,0 +1,45 @@
+use std::ops::AddAssign;
+
+/// Commits and changed lines of a contributor in a single [`Period`](enum.Period.html)
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::{GitDetective, Period};
+///
+/// # fn main() -> Result<(), Error> {
+/// let gd = GitDetective::open(".")?;
+/// for (start, contributors) in gd.timeline(Period::Month)? {
+///   for (contributor, activity) in contributors {
+///     println!(
+///       "{} {}: {} commits, +{} -{}",
+///       Period::Month.label(start),
+///       contributor,
+///       activity.commits,
+///       activity.insertions,
+///       activity.deletions
+///     );
+///   }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Activity {
+    /// Commits credited to the contributor, including co-authored commits
+    pub commits: usize,
+    /// Lines inserted
+    pub insertions: usize,
+    /// Lines deleted
+    pub deletions: usize,
+}
+
+impl AddAssign for Activity {
+    fn add_assign(&mut self, other: Self) {
+        self.commits += other.commits;
+        self.insertions += other.insertions;
+        self.deletions += other.deletions;
+    }
+}
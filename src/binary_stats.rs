@@ -0,0 +1,37 @@
+use std::ops::AddAssign;
+
+/// Binary files changed in commit diffs, which have no lines to count
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::GitDetective;
+///
+/// # fn main() -> Result<(), Error> {
+/// let gd = GitDetective::open(".")?;
+/// for (author, diff_stat) in gd.diff_stats()? {
+///   let binary = diff_stat.binary;
+///   println!("{}: {} binary files, {} bytes", author, binary.added + binary.modified, binary.bytes);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BinaryStats {
+    /// Binary files added
+    pub added: usize,
+    /// Binary files modified, renamed, or copied
+    pub modified: usize,
+    /// Size in bytes of every added and modified binary file
+    pub bytes: usize,
+}
+
+impl AddAssign for BinaryStats {
+    fn add_assign(&mut self, other: Self) {
+        self.added += other.added;
+        self.modified += other.modified;
+        self.bytes += other.bytes;
+    }
+}
@@ -0,0 +1,561 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::read_to_string;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use git2::{
+    BlameOptions, Delta, DiffOptions, ObjectType, Oid, Patch, Repository, TreeWalkMode,
+    TreeWalkResult,
+};
+
+use crate::Error;
+
+/// Minimum characters of a block moved within the same file, the same as `git blame -M`
+const SAME_FILE_MIN_MATCH_CHARACTERS: usize = 20;
+/// Minimum characters of a block moved or copied from another file, the same as `git blame -C`
+const OTHER_FILE_MIN_MATCH_CHARACTERS: usize = 40;
+
+/// Which moved and copied lines are credited to their original author
+///
+/// By default lines are credited to whoever last touched them, so moving a function into another
+/// file takes credit for all of it. libgit2 doesn't implement copy detection, instead blocks of lines
+/// are searched for in the files of the parent commit, the same as `git blame -M -C`.
+///
+/// Lines from ignored revisions, such as running a formatter over the entire project, are credited to
+/// whoever wrote them before, the same as `git blame --ignore-rev`. The repository's `.git-blame-ignore-revs`
+/// and `blame.ignoreRevsFile` are always ignored.
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::{BlameSettings, GitDetective};
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut settings = BlameSettings::new();
+/// settings
+///     .track_copies_same_file(true)
+///     .track_copies_same_commit(true)
+///     .min_match_characters(30);
+///
+/// let mut gd = GitDetective::open(".")?;
+/// gd.set_blame_settings(settings);
+///
+/// let project_stats = gd.final_contributions()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BlameSettings {
+    same_file: bool,
+    same_commit: bool,
+    any_commit: bool,
+    min_match_characters: Option<usize>,
+    ignored_revisions: HashSet<Oid>,
+    /// Set from the [`WhitespaceMode`](enum.WhitespaceMode.html) of [`GitDetective`](struct.GitDetective.html)
+    pub(crate) ignore_whitespace: bool,
+}
+
+/// The commit and author a line of a file is credited to
+#[derive(Clone)]
+pub(crate) struct BlamedLine {
+    pub(crate) commit: Oid,
+    pub(crate) signature: git2::Signature<'static>,
+    /// Path of the file in `commit`
+    path: Rc<Path>,
+    /// Line number in `commit` starting at 0
+    line: usize,
+}
+
+/// Blames of files in earlier commits, shared by every move, copy, and ignored revision
+/// followed while blaming a single file
+type BlameCache = HashMap<(Oid, PathBuf), Rc<Vec<BlamedLine>>>;
+
+/// A file in the parent of a commit, that lines could have been moved or copied from
+struct Source<'repo> {
+    path: PathBuf,
+    blob: git2::Blob<'repo>,
+    min_match_characters: usize,
+}
+
+impl BlameSettings {
+    /// Construct a new BlameSettings that doesn't track any moves or copies
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track lines moved within the same file, the same as `git blame -M`
+    pub fn track_copies_same_file(&mut self, track: bool) -> &mut Self {
+        self.same_file = track;
+        self
+    }
+
+    /// Track lines moved or copied from files modified in the same commit, the same as `git blame -C`
+    pub fn track_copies_same_commit(&mut self, track: bool) -> &mut Self {
+        self.same_commit = track;
+        self
+    }
+
+    /// Track lines copied from any file that existed before the commit, the same as `git blame -C -C -C`
+    ///
+    /// Every file of the parent commit is searched, this is very expensive for large repositories
+    pub fn track_copies_any_commit(&mut self, track: bool) -> &mut Self {
+        self.any_commit = track;
+        self
+    }
+
+    /// Minimum number of alphanumeric characters a moved or copied block must have
+    /// to be credited to its original author
+    ///
+    /// Defaults to 20 for blocks moved within the same file and 40 for blocks from other files, the same as git
+    pub fn min_match_characters(&mut self, characters: usize) -> &mut Self {
+        self.min_match_characters = Some(characters);
+        self
+    }
+
+    /// Credit lines changed in `revision` to whoever wrote them before, the same as `git blame --ignore-rev`
+    pub fn ignore_revision(&mut self, revision: Oid) -> &mut Self {
+        self.ignored_revisions.insert(revision);
+        self
+    }
+
+    /// Ignore all revisions listed in a file, the same as `git blame --ignore-revs-file`
+    ///
+    /// Each line is a full 40 character object name, anything after `#` is a comment.
+    /// Abbreviated object names are rejected, the same as git
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use git_detective::Error;
+    /// use git_detective::{BlameSettings, GitDetective};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut settings = BlameSettings::new();
+    /// settings.ignore_revisions_file("formatting-commits.txt")?;
+    ///
+    /// let mut gd = GitDetective::open(".")?;
+    /// gd.set_blame_settings(settings);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// - Failed to read file [`IOError`](enum.Error.html#variant.IOError)
+    /// - Line isn't a full object name [`GitError`](enum.Error.html#variant.GitError)
+    pub fn ignore_revisions_file<P: AsRef<Path>>(&mut self, path: P) -> Result<&mut Self, Error> {
+        let path = path.as_ref();
+        let buffer = read_to_string(path).map_err(|err| Error::IOError(err, path.into()))?;
+        self.ignore_revisions_buffer(&buffer)?;
+        Ok(self)
+    }
+
+    /// Ignore all revisions listed in the contents of an ignore revisions file
+    ///
+    /// # Errors
+    /// - Line isn't a full object name [`GitError`](enum.Error.html#variant.GitError)
+    pub(crate) fn ignore_revisions_buffer(&mut self, buffer: &str) -> Result<(), Error> {
+        for line in buffer.lines() {
+            let revision = match line.find('#') {
+                Some(index) => &line[..index],
+                None => line,
+            }
+            .trim();
+            if revision.is_empty() {
+                continue;
+            }
+            // `Oid::from_str` pads abbreviated names with zeros, which never match a commit
+            if revision.len() != 40 {
+                return Err(Error::GitError(git2::Error::from_str(&format!(
+                    "invalid object name `{}` in ignore revisions",
+                    revision
+                ))));
+            }
+            self.ignored_revisions.insert(Oid::from_str(revision)?);
+        }
+        Ok(())
+    }
+
+    /// Whether or not any moves or copies are tracked
+    fn tracks_copies(&self) -> bool {
+        self.same_file || self.same_commit || self.any_commit
+    }
+
+    /// Blame a file at `newest_commit` or `HEAD`, indexed by line number starting at 0
+    ///
+    /// # Errors
+    /// - Failed to git blame [`GitError`](enum.Error.html#variant.GitError)
+    pub(crate) fn blame(
+        &self,
+        repository: &Repository,
+        path: &Path,
+        newest_commit: Option<Oid>,
+    ) -> Result<Vec<BlamedLine>, Error> {
+        self.blame_with(repository, path, newest_commit, &mut BlameCache::new())
+    }
+
+    /// Blame a file, reusing the blames of earlier commits in `cache`
+    fn blame_with(
+        &self,
+        repository: &Repository,
+        path: &Path,
+        newest_commit: Option<Oid>,
+        cache: &mut BlameCache,
+    ) -> Result<Vec<BlamedLine>, Error> {
+        let mut options = BlameOptions::new();
+        options.ignore_whitespace(self.ignore_whitespace);
+        if let Some(commit) = newest_commit {
+            options.newest_commit(commit);
+        }
+        let blame = repository.blame_file(path, Some(&mut options))?;
+        let mut lines = BlamedLine::from_blame(&blame, path);
+        if self.tracks_copies() {
+            self.track_copies(repository, path, newest_commit, &mut lines, cache)?;
+        }
+        if !self.ignored_revisions.is_empty() {
+            self.skip_ignored_revisions(repository, &mut lines, cache)?;
+        }
+        Ok(lines)
+    }
+
+    /// Blame a file at an earlier `commit`, each file is only blamed once per commit
+    ///
+    /// Without the cache every line found in a parent blames that parent again,
+    /// which grows exponentially with the length of the history
+    fn blame_earlier(
+        &self,
+        repository: &Repository,
+        path: &Path,
+        commit: Oid,
+        cache: &mut BlameCache,
+    ) -> Result<Rc<Vec<BlamedLine>>, Error> {
+        let key = (commit, path.to_path_buf());
+        if let Some(lines) = cache.get(&key) {
+            return Ok(Rc::clone(lines));
+        }
+        let lines = Rc::new(self.blame_with(repository, path, Some(commit), cache)?);
+        cache.insert(key, Rc::clone(&lines));
+        Ok(lines)
+    }
+
+    /// Credit lines blamed on an ignored revision to the blame of its parent
+    ///
+    /// Each line is mapped to the parent through the changes made in the ignored revision,
+    /// lines that were only added remain credited to the ignored revision
+    fn skip_ignored_revisions(
+        &self,
+        repository: &Repository,
+        lines: &mut [BlamedLine],
+        cache: &mut BlameCache,
+    ) -> Result<(), Error> {
+        let mut ignored: HashMap<(Oid, Rc<Path>), Vec<usize>> = HashMap::new();
+        for (index, line) in lines.iter().enumerate() {
+            if self.ignored_revisions.contains(&line.commit) {
+                let key = (line.commit, Rc::clone(&line.path));
+                ignored.entry(key).or_default().push(index);
+            }
+        }
+
+        for ((commit, path), indexes) in ignored {
+            let commit = repository.find_commit(commit)?;
+            let parent = match commit.parent(0) {
+                Ok(parent) => parent,
+                Err(_) => continue,
+            };
+            let old_blob = match parent.tree()?.get_path(&path) {
+                Ok(entry) => repository.find_blob(entry.id())?,
+                // Created in the ignored revision
+                Err(_) => continue,
+            };
+            let new_blob = repository.find_blob(commit.tree()?.get_path(&path)?.id())?;
+            let mut options = DiffOptions::new();
+            options
+                .context_lines(0)
+                .ignore_whitespace(self.ignore_whitespace);
+            let patch = Patch::from_blobs(&old_blob, None, &new_blob, None, Some(&mut options))?;
+            let mut hunks = Vec::new();
+            for hunk in 0..patch.num_hunks() {
+                let (hunk, _) = patch.hunk(hunk)?;
+                hunks.push((
+                    hunk.old_start() as usize,
+                    hunk.old_lines() as usize,
+                    hunk.new_start() as usize,
+                    hunk.new_lines() as usize,
+                ));
+            }
+
+            let parent_blame = self.blame_earlier(repository, &path, parent.id(), cache)?;
+            for index in indexes {
+                let parent_line = match parent_line(&hunks, lines[index].line) {
+                    Some(parent_line) => parent_line,
+                    None => continue,
+                };
+                if let Some(original) = parent_blame.get(parent_line) {
+                    lines[index] = original.clone();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Credit blocks of lines found in the parent of the commit they're blamed on to the blame of that parent
+    fn track_copies(
+        &self,
+        repository: &Repository,
+        path: &Path,
+        newest_commit: Option<Oid>,
+        lines: &mut [BlamedLine],
+        cache: &mut BlameCache,
+    ) -> Result<(), Error> {
+        let newest_commit = match newest_commit {
+            Some(commit) => repository.find_commit(commit)?,
+            None => repository.head()?.peel_to_commit()?,
+        };
+        let blob = newest_commit
+            .tree()?
+            .get_path(path)?
+            .to_object(repository)?
+            .peel_to_blob()?;
+        let content = split_lines(blob.content());
+
+        let mut blocks: HashMap<Oid, Vec<Range<usize>>> = HashMap::new();
+        let mut start = 0;
+        while start < lines.len() {
+            let commit = lines[start].commit;
+            let end = (start..lines.len())
+                .find(|&line| lines[line].commit != commit)
+                .unwrap_or(lines.len());
+            blocks.entry(commit).or_default().push(start..end);
+            start = end;
+        }
+
+        for (commit, blocks) in blocks {
+            let commit = repository.find_commit(commit)?;
+            let parent = match commit.parent(0) {
+                Ok(parent) => parent,
+                Err(_) => continue,
+            };
+            let sources = self.sources(repository, path, &commit, &parent)?;
+            let source_lines: Vec<Vec<&[u8]>> = sources
+                .iter()
+                .map(|source| split_lines(source.blob.content()))
+                .collect();
+            let indexes: Vec<HashMap<&[u8], Vec<usize>>> = source_lines
+                .iter()
+                .map(|source| {
+                    let mut index: HashMap<&[u8], Vec<usize>> = HashMap::new();
+                    for (number, line) in source.iter().enumerate() {
+                        index.entry(*line).or_default().push(number);
+                    }
+                    index
+                })
+                .collect();
+
+            for block in blocks {
+                let mut line = block.start;
+                while line < block.end {
+                    let found =
+                        best_match(&content, line..block.end, &sources, &source_lines, &indexes);
+                    let (source, source_start, length) = match found {
+                        Some(found) => found,
+                        None => {
+                            line += 1;
+                            continue;
+                        }
+                    };
+                    let source_blame =
+                        self.blame_earlier(repository, &sources[source].path, parent.id(), cache)?;
+                    for offset in 0..length {
+                        if let Some(original) = source_blame.get(source_start + offset) {
+                            lines[line + offset] = original.clone();
+                        }
+                    }
+                    line += length;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Files in `parent` that lines of `path` in `commit` could have come from
+    fn sources<'repo>(
+        &self,
+        repository: &'repo Repository,
+        path: &Path,
+        commit: &git2::Commit<'repo>,
+        parent: &git2::Commit<'repo>,
+    ) -> Result<Vec<Source<'repo>>, Error> {
+        let parent_tree = parent.tree()?;
+        let same_file_characters = self
+            .min_match_characters
+            .unwrap_or(SAME_FILE_MIN_MATCH_CHARACTERS);
+        let other_file_characters = self
+            .min_match_characters
+            .unwrap_or(OTHER_FILE_MIN_MATCH_CHARACTERS);
+
+        let mut paths: Vec<(PathBuf, Oid, usize)> = Vec::new();
+        if self.same_file {
+            if let Ok(entry) = parent_tree.get_path(path) {
+                paths.push((path.into(), entry.id(), same_file_characters));
+            }
+        }
+        if self.same_commit {
+            let tree = commit.tree()?;
+            let diff = repository.diff_tree_to_tree(Some(&parent_tree), Some(&tree), None)?;
+            for delta in diff.deltas() {
+                let old_file = delta.old_file();
+                let old_path = match (delta.status(), old_file.path()) {
+                    (Delta::Modified, Some(old_path))
+                    | (Delta::Deleted, Some(old_path))
+                    | (Delta::Renamed, Some(old_path)) => old_path,
+                    _ => continue,
+                };
+                if old_path != path {
+                    paths.push((old_path.into(), old_file.id(), other_file_characters));
+                }
+            }
+        }
+        if self.any_commit {
+            let mut seen: HashSet<PathBuf> =
+                paths.iter().map(|(path, _, _)| path.clone()).collect();
+            seen.insert(path.into());
+            parent_tree.walk(TreeWalkMode::PreOrder, |directory, entry| {
+                if let (Some(ObjectType::Blob), Some(name)) = (entry.kind(), entry.name()) {
+                    let entry_path = Path::new(directory).join(name);
+                    if seen.insert(entry_path.clone()) {
+                        paths.push((entry_path, entry.id(), other_file_characters));
+                    }
+                }
+                TreeWalkResult::Ok
+            })?;
+        }
+
+        Ok(paths
+            .into_iter()
+            .filter_map(|(path, id, min_match_characters)| {
+                let blob = repository.find_blob(id).ok()?;
+                if blob.is_binary() {
+                    return None;
+                }
+                Some(Source {
+                    path,
+                    blob,
+                    min_match_characters,
+                })
+            })
+            .collect())
+    }
+}
+
+/// Line number in the parent of a line starting at 0, given the hunks of a diff without context
+///
+/// Hunks are `(old_start, old_lines, new_start, new_lines)` with line numbers starting at 1.
+/// Changed lines are paired with the removed lines in the same position of their hunk.
+fn parent_line(hunks: &[(usize, usize, usize, usize)], line: usize) -> Option<usize> {
+    let line = line + 1;
+    let mut old_line = line;
+    for &(old_start, old_lines, new_start, new_lines) in hunks {
+        // Empty sides of a hunk start at the line before it
+        let old_start = if old_lines == 0 {
+            old_start + 1
+        } else {
+            old_start
+        };
+        let new_start = if new_lines == 0 {
+            new_start + 1
+        } else {
+            new_start
+        };
+        if line < new_start {
+            break;
+        }
+        if line < new_start + new_lines {
+            if old_lines == 0 {
+                return None;
+            }
+            let offset = (line - new_start).min(old_lines - 1);
+            return Some(old_start + offset - 1);
+        }
+        old_line = line + (old_start + old_lines) - (new_start + new_lines);
+    }
+    old_line.checked_sub(1)
+}
+
+/// Split the contents of a blob into lines
+fn split_lines(content: &[u8]) -> Vec<&[u8]> {
+    content.split(|byte| *byte == b'\n').collect()
+}
+
+/// Longest block of `lines` starting at `lines.start` that is found in one of `sources`
+///
+/// Returns the index of the source, the line number in the source, and the length of the block
+fn best_match(
+    content: &[&[u8]],
+    lines: Range<usize>,
+    sources: &[Source<'_>],
+    source_lines: &[Vec<&[u8]>],
+    indexes: &[HashMap<&[u8], Vec<usize>>],
+) -> Option<(usize, usize, usize)> {
+    let first = content.get(lines.start)?;
+    // Blank lines match everywhere, they're only included when they extend a block
+    if first.iter().all(u8::is_ascii_whitespace) {
+        return None;
+    }
+    let mut best = None;
+    let mut best_length = 0;
+    for (source, index) in indexes.iter().enumerate() {
+        let starts = match index.get(first) {
+            Some(starts) => starts,
+            None => continue,
+        };
+        for &source_start in starts {
+            let length = (0..lines.len())
+                .take_while(|offset| {
+                    match (
+                        content.get(lines.start + offset),
+                        source_lines[source].get(source_start + offset),
+                    ) {
+                        (Some(line), Some(source_line)) => line == source_line,
+                        _ => false,
+                    }
+                })
+                .count();
+            let characters: usize = content[lines.start..lines.start + length]
+                .iter()
+                .map(|line| {
+                    line.iter()
+                        .filter(|byte| byte.is_ascii_alphanumeric())
+                        .count()
+                })
+                .sum();
+            if length > best_length && characters >= sources[source].min_match_characters {
+                best = Some((source, source_start, length));
+                best_length = length;
+            }
+        }
+    }
+    best
+}
+
+impl BlamedLine {
+    /// Expand each hunk of a blame of `path` into its lines
+    fn from_blame(blame: &git2::Blame<'_>, path: &Path) -> Vec<Self> {
+        blame
+            .iter()
+            .flat_map(|hunk| {
+                let commit = hunk.final_commit_id();
+                let signature = hunk.final_signature().to_owned();
+                let path: Rc<Path> = Rc::from(hunk.path().unwrap_or(path));
+                // Line numbers start at 1
+                let start = hunk.orig_start_line().saturating_sub(1);
+                (0..hunk.lines_in_hunk()).map(move |offset| BlamedLine {
+                    commit,
+                    signature: signature.clone(),
+                    path: Rc::clone(&path),
+                    line: start + offset,
+                })
+            })
+            .collect()
+    }
+}
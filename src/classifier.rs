@@ -0,0 +1,158 @@
+use std::path::Path;
+
+use git2::{ObjectType, Repository, TreeWalkMode, TreeWalkResult};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+
+use crate::heuristics;
+use crate::{Error, FileCategory};
+
+/// Categories that can be set by `.gitattributes`, in order of precedence
+const ATTRIBUTE_CATEGORIES: [FileCategory; 4] = [
+    FileCategory::Binary,
+    FileCategory::Generated,
+    FileCategory::Vendored,
+    FileCategory::Documentation,
+];
+
+/// Decides the [`FileCategory`](enum.FileCategory.html) of files in a revision
+pub(crate) struct Classifier {
+    attributes: Vec<(FileCategory, Gitignore)>,
+    heuristics: bool,
+}
+
+impl Classifier {
+    /// Classifier that treats every file as source code
+    pub(crate) fn empty() -> Self {
+        Self {
+            attributes: Vec::new(),
+            heuristics: false,
+        }
+    }
+
+    /// Guess the category of files without a set attribute from their path and contents
+    pub(crate) fn heuristics(self, heuristics: bool) -> Self {
+        Self { heuristics, ..self }
+    }
+
+    /// Read every `.gitattributes` in `tree`
+    ///
+    /// Files in deeper directories take precedence, the same as git
+    ///
+    /// # Errors
+    /// - Failed to read tree [`GitError`](enum.Error.html#variant.GitError)
+    /// - Pattern isn't a valid glob [`IgnoreError`](enum.Error.html#variant.IgnoreError)
+    pub(crate) fn from_tree(repository: &Repository, tree: &git2::Tree<'_>) -> Result<Self, Error> {
+        let mut attribute_files = Vec::new();
+        tree.walk(TreeWalkMode::PreOrder, |directory, entry| {
+            if entry.kind() == Some(ObjectType::Blob) && entry.name() == Some(".gitattributes") {
+                attribute_files.push((directory.to_string(), entry.id()));
+            }
+            TreeWalkResult::Ok
+        })?;
+        attribute_files.sort_by_key(|(directory, _)| directory.matches('/').count());
+
+        let mut builders: Vec<(FileCategory, GitignoreBuilder)> = ATTRIBUTE_CATEGORIES
+            .iter()
+            .map(|category| (*category, GitignoreBuilder::new(".")))
+            .collect();
+        for (directory, id) in attribute_files {
+            let blob = repository.find_blob(id)?;
+            let buffer = String::from_utf8_lossy(blob.content());
+            for line in buffer.lines() {
+                let mut parts = line.split_whitespace();
+                let pattern = match parts.next() {
+                    Some(pattern) if !pattern.starts_with('#') => pattern,
+                    _ => continue,
+                };
+                let pattern = anchor(&directory, pattern);
+                for attribute in parts {
+                    for (category, set) in parse_attribute(attribute) {
+                        if let Some((_, builder)) = builders
+                            .iter_mut()
+                            .find(|(builder_category, _)| *builder_category == category)
+                        {
+                            // Unset attributes are whitelisted so later lines override earlier ones
+                            let line = if set {
+                                pattern.clone()
+                            } else {
+                                format!("!{}", pattern)
+                            };
+                            builder.add_line(None, &line)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut attributes = Vec::new();
+        for (category, builder) in builders {
+            attributes.push((category, builder.build()?));
+        }
+        Ok(Self {
+            attributes,
+            heuristics: false,
+        })
+    }
+
+    /// Category of a path relative to the root of the repository
+    ///
+    /// Without a set attribute the category is guessed from the path and `content` when heuristics are enabled
+    pub(crate) fn category<P: AsRef<Path>>(&self, path: P, content: Option<&[u8]>) -> FileCategory {
+        let path = path.as_ref();
+        let mut unset = Vec::new();
+        for (category, matcher) in self.attributes.iter() {
+            match matcher.matched(path, false) {
+                Match::Ignore(_) => return *category,
+                Match::Whitelist(_) => unset.push(*category),
+                Match::None => {}
+            }
+        }
+        if !self.heuristics {
+            return FileCategory::Source;
+        }
+        match heuristics::detect(path, content) {
+            Some(FileCategory::Minified) if unset.contains(&FileCategory::Generated) => {
+                FileCategory::Source
+            }
+            Some(category) if !unset.contains(&category) => category,
+            _ => FileCategory::Source,
+        }
+    }
+}
+
+/// Make a pattern from a `.gitattributes` in `directory` relative to the root of the repository
+///
+/// Patterns without a slash match at any depth below their directory
+fn anchor(directory: &str, pattern: &str) -> String {
+    if pattern.contains('/') {
+        format!("/{}{}", directory, pattern.trim_start_matches('/'))
+    } else if directory.is_empty() {
+        pattern.to_string()
+    } else {
+        format!("/{}**/{}", directory, pattern)
+    }
+}
+
+/// Categories set or unset by a single attribute such as `linguist-generated`, `-diff`, or `binary`
+fn parse_attribute(attribute: &str) -> Vec<(FileCategory, bool)> {
+    let (name, set) = if let Some(name) = attribute.strip_prefix('-') {
+        (name, false)
+    } else if let Some(name) = attribute.strip_prefix('!') {
+        (name, false)
+    } else {
+        match attribute.find('=') {
+            Some(index) => (&attribute[..index], &attribute[index + 1..] != "false"),
+            None => (attribute, true),
+        }
+    };
+    match name {
+        "binary" if set => vec![(FileCategory::Binary, true)],
+        // Only `-diff` marks a file as binary, `diff=driver` sets a diff driver
+        "diff" => vec![(FileCategory::Binary, attribute.starts_with('-'))],
+        "linguist-generated" => vec![(FileCategory::Generated, set)],
+        "linguist-vendored" => vec![(FileCategory::Vendored, set)],
+        "linguist-documentation" => vec![(FileCategory::Documentation, set)],
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,164 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A canonical contributor to a Git Repository
+///
+/// Two contributors are the same person when they share the same canonical name,
+/// emails are only collected to show every identity that was resolved to them.
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::GitDetective;
+///
+/// # fn main() -> Result<(), Error> {
+/// let gd = GitDetective::open(".")?;
+/// for contributor in gd.contributors()? {
+///   println!("{} {:?}", contributor.name(), contributor.emails());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Contributor {
+    name: String,
+    emails: BTreeSet<String>,
+}
+
+impl Contributor {
+    /// Construct a new Contributor without any known emails
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            emails: BTreeSet::new(),
+        }
+    }
+
+    /// Add a known email to Contributor
+    pub fn with_email<S: Into<String>>(mut self, email: S) -> Self {
+        let email = email.into();
+        if !email.is_empty() {
+            self.emails.insert(email);
+        }
+        self
+    }
+
+    /// Canonical name of Contributor
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// All known emails of Contributor
+    pub fn emails(&self) -> &BTreeSet<String> {
+        &self.emails
+    }
+
+    /// Merge the emails of the same Contributor
+    pub(crate) fn merge(&mut self, other: Contributor) {
+        self.emails.extend(other.emails);
+    }
+}
+
+/// Get the value for `contributor`, merging emails with the existing key
+///
+/// `HashMap` doesn't allow mutating keys so the entry is reinserted
+pub(crate) fn entry<V: Default>(
+    map: &mut HashMap<Contributor, V>,
+    mut contributor: Contributor,
+) -> &mut V {
+    let value = match map.remove_entry(contributor.name()) {
+        Some((existing, value)) => {
+            contributor.merge(existing);
+            value
+        }
+        None => V::default(),
+    };
+    let name = contributor.name.clone();
+    map.insert(contributor, value);
+    // Safe to unwrap, was just inserted
+    map.get_mut(name.as_str()).unwrap()
+}
+
+/// Insert `contributor`, merging emails with the existing entry
+pub(crate) fn insert(set: &mut HashSet<Contributor>, mut contributor: Contributor) {
+    if let Some(existing) = set.take(contributor.name()) {
+        contributor.merge(existing);
+    }
+    set.insert(contributor);
+}
+
+impl PartialEq for Contributor {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Contributor {}
+
+impl PartialEq<str> for Contributor {
+    fn eq(&self, other: &str) -> bool {
+        self.name == other
+    }
+}
+
+impl PartialEq<&str> for Contributor {
+    fn eq(&self, other: &&str) -> bool {
+        self.name == *other
+    }
+}
+
+impl Hash for Contributor {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl PartialOrd for Contributor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Contributor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.name.cmp(&other.name)
+    }
+}
+
+impl Borrow<str> for Contributor {
+    fn borrow(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for Contributor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Contributors are serialized as their canonical name, so they can be used as keys
+#[cfg(feature = "serde")]
+impl serde::Serialize for Contributor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name)
+    }
+}
+
+#[doc(hidden)]
+impl From<&str> for Contributor {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+#[doc(hidden)]
+impl From<String> for Contributor {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
@@ -0,0 +1,33 @@
+/// How co-authors from `Co-authored-by:` trailers are credited
+///
+/// Applies to [`contributors`](struct.GitDetective.html#method.contributors),
+/// [`diff_stats`](struct.GitDetective.html#method.diff_stats), and
+/// [`files_contributed_to`](struct.GitDetective.html#method.files_contributed_to)
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::{CreditPolicy, GitDetective};
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut gd = GitDetective::open(".")?;
+/// gd.set_credit_policy(CreditPolicy::SplitEvenly);
+///
+/// let diff_stats = gd.diff_stats()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum CreditPolicy {
+    /// Only the author of a commit is credited, co-authors are ignored
+    #[default]
+    AuthorOnly,
+    /// Insertions, deletions, and binary bytes are divided evenly between the author and all co-authors
+    ///
+    /// Remainders are handed out starting with the author. Merges, renames, copies, and binary files
+    /// can't be divided, everyone is credited with all of them
+    SplitEvenly,
+    /// The author and all co-authors are credited with every insertion and deletion
+    FullCredit,
+}
@@ -1,4 +1,8 @@
+use std::collections::BTreeMap;
 use std::ops::AddAssign;
+use std::path::PathBuf;
+
+use crate::{BinaryStats, FileCategory, Stats, WhitespaceMode};
 
 /// Insertion and Deletion statistics for Commit diffs
 ///
@@ -13,17 +17,192 @@ use std::ops::AddAssign;
 /// let diff_stats = gd.diff_stats()?;
 /// for (author, diff_stat) in diff_stats {
 ///   println!("{}: +{} -{}", author, diff_stat.insertions, diff_stat.deletions);
+///   println!("{}: +{} comments", author, diff_stat.inserted.comments);
 /// }
 ///
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DiffStats {
     /// Lines of code inserted
     pub insertions: usize,
     /// Lines of code deleted
     pub deletions: usize,
+    /// Inserted lines split into code, comments, and blanks, `inserted.lines` is the same as `insertions`
+    pub inserted: Stats,
+    /// Deleted lines split into code, comments, and blanks, `deleted.lines` is the same as `deletions`
+    pub deleted: Stats,
+    /// Binary files changed, they're never counted as insertions or deletions and are counted
+    /// whatever the [`CategoryPolicy`](enum.CategoryPolicy.html)
+    pub binary: BinaryStats,
+    /// Files renamed, only the lines that changed are counted as insertions and deletions
+    pub renames: usize,
+    /// Files copied, only the lines that changed are counted as insertions and deletions
+    pub copies: usize,
+    /// Merge commits, their changes are only counted as the [`MergePolicy`](enum.MergePolicy.html) allows
+    pub merges: usize,
+    /// Whether whitespace-only changes were counted
+    pub whitespace_mode: WhitespaceMode,
+    /// Insertions and deletions in files that aren't source code, only counted when
+    /// [`CategoryPolicy::Separate`](enum.CategoryPolicy.html#variant.Separate) is used
+    pub categories: BTreeMap<FileCategory, DiffStats>,
+    /// Insertions and deletions by language, detected by tokei from the path of each changed file
+    pub languages: BTreeMap<&'static str, DiffStats>,
+}
+
+impl DiffStats {
+    /// Divide into `parts` as evenly as possible, the first parts receive any remainder
+    ///
+    /// Only lines and binary bytes are divided, every part is credited with all of the
+    /// merges, renames, copies, and binary files, which are events rather than amounts
+    pub(crate) fn split(&self, parts: usize) -> Vec<DiffStats> {
+        self.split_from(parts.max(1), 0, 0)
+    }
+
+    /// Divide into `parts`, remainders are handed out starting at `insertions_start` and
+    /// `deletions_start`, wrapping around
+    ///
+    /// Languages continue where the previous language stopped, so each share's languages add up to its total
+    fn split_from(
+        &self,
+        parts: usize,
+        insertions_start: usize,
+        deletions_start: usize,
+    ) -> Vec<DiffStats> {
+        let inserted = split_stats(&self.inserted, parts, insertions_start);
+        let deleted = split_stats(&self.deleted, parts, deletions_start);
+        let mut shares: Vec<DiffStats> = (0..parts)
+            .map(|part| DiffStats {
+                insertions: share(self.insertions, parts, insertions_start, part),
+                deletions: share(self.deletions, parts, deletions_start, part),
+                inserted: inserted[part],
+                deleted: deleted[part],
+                binary: BinaryStats {
+                    bytes: share(self.binary.bytes, parts, 0, part),
+                    ..self.binary
+                },
+                renames: self.renames,
+                copies: self.copies,
+                merges: self.merges,
+                whitespace_mode: self.whitespace_mode,
+                categories: BTreeMap::new(),
+                languages: BTreeMap::new(),
+            })
+            .collect();
+        for (category, stats) in self.categories.iter() {
+            for (share, category_share) in shares.iter_mut().zip(stats.split(parts)) {
+                share.categories.insert(*category, category_share);
+            }
+        }
+        let (mut insertions_start, mut deletions_start) = (insertions_start, deletions_start);
+        for (language, stats) in self.languages.iter() {
+            let language_shares = stats.split_from(parts, insertions_start, deletions_start);
+            for (share, language_share) in shares.iter_mut().zip(language_shares) {
+                share.languages.insert(*language, language_share);
+            }
+            insertions_start = (insertions_start + stats.insertions) % parts;
+            deletions_start = (deletions_start + stats.deletions) % parts;
+        }
+        shares
+    }
+
+    /// Record the [`WhitespaceMode`](enum.WhitespaceMode.html) changes were counted with
+    pub(crate) fn set_whitespace_mode(&mut self, mode: WhitespaceMode) {
+        self.whitespace_mode = mode;
+        for stats in self
+            .categories
+            .values_mut()
+            .chain(self.languages.values_mut())
+        {
+            stats.set_whitespace_mode(mode);
+        }
+    }
+
+    /// Count `inserted` and `deleted` lines of a file in `language`
+    pub(crate) fn add_lines(&mut self, language: &'static str, inserted: Stats, deleted: Stats) {
+        let language_stats = self.languages.entry(language).or_default();
+        language_stats.insertions += inserted.lines;
+        language_stats.deletions += deleted.lines;
+        language_stats.inserted += inserted;
+        language_stats.deleted += deleted;
+        self.insertions += inserted.lines;
+        self.deletions += deleted.lines;
+        self.inserted += inserted;
+        self.deleted += deleted;
+    }
+}
+
+/// Changes to a single file in a commit
+pub(crate) struct FileDiffStats {
+    /// Path after the commit
+    pub(crate) path: PathBuf,
+    /// Path before the commit, only when the file was renamed
+    pub(crate) renamed_from: Option<PathBuf>,
+    pub(crate) category: FileCategory,
+    pub(crate) stats: DiffStats,
+}
+
+/// Share of `total` for `part`, remainders are handed out starting at `start`, wrapping around
+fn share(total: usize, parts: usize, start: usize, part: usize) -> usize {
+    total / parts + usize::from((part + parts - start) % parts < total % parts)
+}
+
+/// Divide `stats` into `parts`, remainders are handed out starting at `start` for blanks, then comments, then code
+///
+/// Each share's blanks, comments, and code add up to its lines
+fn split_stats(stats: &Stats, parts: usize, start: usize) -> Vec<Stats> {
+    let comments_start = (start + stats.blanks) % parts;
+    let code_start = (comments_start + stats.comments) % parts;
+    (0..parts)
+        .map(|part| Stats {
+            lines: share(stats.lines, parts, start, part),
+            blanks: share(stats.blanks, parts, start, part),
+            comments: share(stats.comments, parts, comments_start, part),
+            code: share(stats.code, parts, code_start, part),
+        })
+        .collect()
+}
+
+#[doc(hidden)]
+impl AddAssign for DiffStats {
+    fn add_assign(&mut self, other: Self) {
+        self.insertions += other.insertions;
+        self.deletions += other.deletions;
+        self.inserted += other.inserted;
+        self.deleted += other.deleted;
+        self.binary += other.binary;
+        self.renames += other.renames;
+        self.copies += other.copies;
+        self.merges += other.merges;
+        self.whitespace_mode = other.whitespace_mode;
+        for (category, stats) in other.categories {
+            *self.categories.entry(category).or_default() += stats;
+        }
+        for (language, stats) in other.languages {
+            *self.languages.entry(language).or_default() += stats;
+        }
+    }
+}
+
+#[doc(hidden)]
+impl From<git2::DiffStats> for DiffStats {
+    fn from(stats: git2::DiffStats) -> Self {
+        Self {
+            insertions: stats.insertions(),
+            deletions: stats.deletions(),
+            inserted: Stats::default(),
+            deleted: Stats::default(),
+            binary: BinaryStats::default(),
+            renames: 0,
+            copies: 0,
+            merges: 0,
+            whitespace_mode: WhitespaceMode::default(),
+            categories: BTreeMap::new(),
+            languages: BTreeMap::new(),
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -18,6 +18,11 @@ pub enum Error {
     /// Returned from `Repo::is_clean`
     #[error("Unclean State Error: Repository is in `{0:?}` state, but expected clean")]
     UncleanState(git2::RepositoryState),
+    /// Repository is bare, but the operation requires a working directory
+    ///
+    /// Returned from [`checkout`](struct.GitDetective.html#method.checkout)
+    #[error("Bare Repository Error: operation requires a working directory")]
+    BareRepository,
     /// Git String is not valid UTF-8
     ///
     /// Could be a Branch name, commit hash, etc
@@ -30,4 +35,16 @@ pub enum Error {
     /// [`final_contributions_file`](struct.GitDetective.html#method.final_contributions_file)
     #[error("`{0}` in `{1:?}`")]
     IOError(std::io::Error, PathBuf),
+
+    /// Invalid regular expression
+    ///
+    /// Occurred in [`IdentityMatcher::regex`](enum.IdentityMatcher.html#method.regex)
+    #[error("Regex Error: `{0}`")]
+    RegexError(#[from] regex::Error),
+
+    /// Invalid exclusion pattern
+    ///
+    /// Occurred in [`exclude`](struct.GitDetective.html#method.exclude) or while reading `.gitdetectiveignore`
+    #[error("Ignore Pattern Error: `{0}`")]
+    IgnoreError(#[from] ignore::Error),
 }
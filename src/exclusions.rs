@@ -0,0 +1,51 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::Error;
+
+/// Files excluded from [`ls`](struct.GitDetective.html#method.ls),
+/// [`final_contributions`](struct.GitDetective.html#method.final_contributions),
+/// [`diff_stats`](struct.GitDetective.html#method.diff_stats), and
+/// [`files_contributed_to`](struct.GitDetective.html#method.files_contributed_to)
+///
+/// Combines exact paths with patterns using [gitignore](https://git-scm.com/docs/gitignore) syntax
+pub(crate) struct Exclusions {
+    files: HashSet<String>,
+    patterns: Gitignore,
+}
+
+impl Exclusions {
+    /// Compile `patterns`, patterns that appear later take precedence
+    ///
+    /// # Errors
+    /// - Pattern isn't a valid glob [`IgnoreError`](enum.Error.html#variant.IgnoreError)
+    pub(crate) fn new<'a, I>(files: HashSet<String>, patterns: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut builder = GitignoreBuilder::new(".");
+        for pattern in patterns {
+            builder.add_line(None, pattern)?;
+        }
+        Ok(Self {
+            files,
+            patterns: builder.build()?,
+        })
+    }
+
+    /// Whether or not a path relative to the root of the repository is excluded
+    pub(crate) fn is_excluded<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        let excluded_file = match path.to_str() {
+            Some(path) => self.files.contains(path),
+            None => false,
+        };
+        excluded_file
+            || self
+                .patterns
+                .matched_path_or_any_parents(path, false)
+                .is_ignore()
+    }
+}
@@ -0,0 +1,75 @@
+/// Kind of file, which decides whether its lines are credited like source code
+///
+/// Files are categorized by `.gitattributes` at the analysed revision, the same as
+/// [linguist](https://github.com/github/linguist/blob/master/docs/overrides.md)
+///
+/// With [`set_heuristics`](struct.GitDetective.html#method.set_heuristics), files without attributes
+/// are categorized by their path and contents, lockfiles and files with a header comment such as
+/// `// @generated` are generated, files in directories such as `node_modules` or `vendor` are
+/// vendored, and files with extremely long lines are minified. Unsetting an attribute, such as
+/// `-linguist-generated`, overrides the guess
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum FileCategory {
+    /// Written by contributors, always counted
+    #[default]
+    Source,
+    /// Marked `linguist-generated`
+    Generated,
+    /// Marked `linguist-vendored`
+    Vendored,
+    /// Marked `linguist-documentation`
+    Documentation,
+    /// Minified bundles such as `*.min.js`, unset with `-linguist-generated`
+    Minified,
+    /// Marked `binary` or `-diff`
+    Binary,
+}
+
+/// What to do with files that aren't [`FileCategory::Source`](enum.FileCategory.html#variant.Source)
+///
+/// Applies to [`final_contributions`](struct.GitDetective.html#method.final_contributions) and
+/// [`diff_stats`](struct.GitDetective.html#method.diff_stats)
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::{CategoryPolicy, FileCategory, GitDetective};
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut gd = GitDetective::open(".")?;
+/// gd.set_category_policy(CategoryPolicy::Separate);
+///
+/// let project_stats = gd.final_contributions()?;
+/// if let Some(generated) = project_stats.category(FileCategory::Generated) {
+///     println!("{} generated lines", generated.total_lines());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum CategoryPolicy {
+    /// Files are dropped from all statistics
+    #[default]
+    Exclude,
+    /// Files are counted in a separate bucket for their category
+    Separate,
+    /// Files are counted the same as source code
+    Include,
+}
+
+impl CategoryPolicy {
+    /// Category that files of `category` are counted in, `None` when they're dropped
+    pub(crate) fn bucket(self, category: FileCategory) -> Option<FileCategory> {
+        match (self, category) {
+            (_, FileCategory::Source) | (CategoryPolicy::Include, _) => Some(FileCategory::Source),
+            (CategoryPolicy::Separate, category) => Some(category),
+            (CategoryPolicy::Exclude, _) => None,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+
+use crate::contributor::{self, Contributor};
+use crate::{DiffStats, FileCategory};
+
+/// How often and by whom a single file was changed
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::GitDetective;
+///
+/// # fn main() -> Result<(), Error> {
+/// let gd = GitDetective::open(".")?;
+/// for (path, churn) in gd.file_churn()? {
+///   println!(
+///     "{}: {} commits, +{} -{} by {} authors",
+///     path.display(),
+///     churn.commits,
+///     churn.insertions,
+///     churn.deletions,
+///     churn.authors()
+///   );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FileChurn {
+    /// Number of commits that changed the file
+    pub commits: usize,
+    /// Lines inserted by all commits
+    pub insertions: usize,
+    /// Lines deleted by all commits
+    pub deletions: usize,
+    /// Category of the file, always [`FileCategory::Source`](enum.FileCategory.html#variant.Source)
+    /// unless [`CategoryPolicy::Separate`](enum.CategoryPolicy.html#variant.Separate) is used
+    pub category: FileCategory,
+    /// Paths the file had before it was renamed, oldest commits are counted under the newest path
+    pub previous_paths: BTreeSet<PathBuf>,
+    /// Insertions and deletions by each contributor
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::serialize::sorted"))]
+    pub contributors: HashMap<Contributor, DiffStats>,
+}
+
+impl FileChurn {
+    /// Number of distinct contributors that changed the file
+    pub fn authors(&self) -> usize {
+        self.contributors.len()
+    }
+
+    /// Count a commit that changed the file
+    pub(crate) fn add_commit<I>(&mut self, stats: &DiffStats, shares: I)
+    where
+        I: IntoIterator<Item = (Contributor, DiffStats)>,
+    {
+        self.commits += 1;
+        self.insertions += stats.insertions;
+        self.deletions += stats.deletions;
+        for (contributor, share) in shares {
+            *contributor::entry(&mut self.contributors, contributor) += share;
+        }
+    }
+}
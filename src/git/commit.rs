@@ -76,6 +76,92 @@ impl<'repo> Commit<'_> {
         let naive = NaiveDateTime::from_timestamp(timestamp, 0);
         DateTime::<Utc>::from_utc(naive, Utc)
     }
+
+    /// Co-authors credited with `Co-authored-by:` trailers in the commit message
+    ///
+    /// Co-authors don't have their own timestamp, so they use the time of the commit
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::GitDetective;
+    /// # fn main() -> Result<(), Error> {
+    ///
+    /// let gd = GitDetective::open(".")?;
+    /// for commit in gd.commits()? {
+    ///   for co_author in commit.co_authors() {
+    ///     println!("{} paired with {}", commit.author().name()?, co_author.name()?);
+    ///   }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn co_authors(&self) -> Vec<Signature<'static>> {
+        let time = self.inner.time();
+        co_authors(self.inner.message_raw_bytes())
+            .into_iter()
+            .filter_map(|(name, email)| git2::Signature::new(&name, &email, &time).ok())
+            .map(Signature::from)
+            .collect()
+    }
+}
+
+/// Parse the names and emails of all `Co-authored-by: Name <email>` trailers
+///
+/// Only the trailer block is parsed, lines in the rest of the message are never credited
+pub(crate) fn co_authors(message: &[u8]) -> Vec<(String, String)> {
+    const TRAILER: &str = "co-authored-by:";
+    let message = String::from_utf8_lossy(message);
+    trailers(&message)
+        .into_iter()
+        .map(str::trim)
+        .filter(|line| {
+            line.len() > TRAILER.len()
+                && line.is_char_boundary(TRAILER.len())
+                && line[..TRAILER.len()].eq_ignore_ascii_case(TRAILER)
+        })
+        .filter_map(|line| {
+            let identity = &line[TRAILER.len()..];
+            let start = identity.find('<')?;
+            let end = start + identity[start..].find('>')?;
+            let name = identity[..start].trim();
+            let email = identity[start + 1..end].trim();
+            if name.is_empty() {
+                return None;
+            }
+            Some((name.to_string(), email.to_string()))
+        })
+        .collect()
+}
+
+/// Lines of the trailer block, the last paragraph of `message` if every line in it is a
+/// `Token: value` trailer or a continuation of one, the same as `git interpret-trailers`
+///
+/// The subject is never a trailer block
+fn trailers(message: &str) -> Vec<&str> {
+    let lines: Vec<&str> = message.lines().map(str::trim_end).collect();
+    let end = match lines.iter().rposition(|line| !line.is_empty()) {
+        Some(last) => last + 1,
+        None => return Vec::new(),
+    };
+    let start = match lines[..end].iter().rposition(|line| line.is_empty()) {
+        Some(blank) => blank + 1,
+        None => return Vec::new(),
+    };
+    let block = &lines[start..end];
+    let is_trailer = |line: &&str| match line.find(':') {
+        Some(colon) => colon > 0 && !line[..colon].contains(char::is_whitespace),
+        None => false,
+    };
+    let continues = |line: &&str| line.starts_with(char::is_whitespace);
+    if matches!(block.first(), Some(line) if is_trailer(line))
+        && block.iter().all(|line| is_trailer(line) || continues(line))
+    {
+        block.to_vec()
+    } else {
+        Vec::new()
+    }
 }
 
 impl<'repo> GitReference<'repo> for Commit<'repo> {
@@ -1,9 +1,11 @@
 /// The Status of a file
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct FileStatus {
     /// Path to a file
     pub path: String,
-    /// Status of a file
+    /// Status of a file, serialized as a list of flags such as `["index_new", "wt_modified"]`
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::serialize::status"))]
     pub status: git2::Status,
 }
 
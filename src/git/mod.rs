@@ -15,3 +15,6 @@ pub use signature::Signature;
 
 pub(crate) mod file_status;
 pub use file_status::FileStatus;
+
+pub(crate) mod record;
+pub use record::{CommitRecord, SignatureRecord, TagRecord};
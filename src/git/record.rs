@@ -0,0 +1,126 @@
+use std::string::FromUtf8Error;
+
+use chrono::{DateTime, Utc};
+
+use crate::git::GitReference;
+use crate::{Commit, Signature, Tag};
+
+/// Replace invalid UTF-8 instead of failing, records are for reporting
+fn lossy(string: Result<String, FromUtf8Error>) -> String {
+    string.unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+/// An owned copy of a [`Signature`](struct.Signature.html)
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SignatureRecord {
+    /// Name, invalid UTF-8 is replaced
+    pub name: String,
+    /// Email, invalid UTF-8 is replaced
+    pub email: String,
+    /// Date signed, serialized in RFC 3339
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::serialize::date"))]
+    pub date: DateTime<Utc>,
+}
+
+impl From<&Signature<'_>> for SignatureRecord {
+    fn from(signature: &Signature<'_>) -> Self {
+        Self {
+            name: lossy(signature.name()),
+            email: lossy(signature.email()),
+            date: signature.date(),
+        }
+    }
+}
+
+/// An owned copy of a [`Commit`](struct.Commit.html) that outlives the repository
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::{CommitRecord, GitDetective};
+///
+/// # fn main() -> Result<(), Error> {
+/// let gd = GitDetective::open(".")?;
+/// let records: Vec<CommitRecord> = gd.commits()?.map(|commit| (&commit).into()).collect();
+/// drop(gd);
+///
+/// for record in records {
+///   println!("{} {}", record.id, record.author.name);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CommitRecord {
+    /// Full hex id of the commit
+    pub id: String,
+    /// Author of the commit
+    pub author: SignatureRecord,
+    /// Committer of the commit
+    pub committer: SignatureRecord,
+    /// Co-authors credited with `Co-authored-by:` trailers
+    pub co_authors: Vec<SignatureRecord>,
+    /// Summary of the commit message, invalid UTF-8 is replaced
+    pub summary: Option<String>,
+    /// Date commited, serialized in RFC 3339
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::serialize::date"))]
+    pub date: DateTime<Utc>,
+}
+
+impl From<&Commit<'_>> for CommitRecord {
+    fn from(commit: &Commit<'_>) -> Self {
+        Self {
+            id: commit.id().to_string(),
+            author: (&commit.author()).into(),
+            committer: (&commit.committer()).into(),
+            co_authors: commit
+                .co_authors()
+                .iter()
+                .map(SignatureRecord::from)
+                .collect(),
+            summary: commit.summary().map(lossy),
+            date: commit.date(),
+        }
+    }
+}
+
+/// An owned copy of a [`Tag`](struct.Tag.html) that outlives the repository
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::{GitDetective, TagRecord};
+///
+/// # fn main() -> Result<(), Error> {
+/// let gd = GitDetective::open(".")?;
+/// let records: Vec<TagRecord> = gd.tags()?.iter().map(TagRecord::from).collect();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TagRecord {
+    /// Name of the tag, invalid UTF-8 is replaced
+    pub name: String,
+    /// Full hex id of the tag
+    pub id: String,
+    /// Message of the tag, invalid UTF-8 is replaced
+    pub message: Option<String>,
+    /// Creator of the tag
+    pub tagger: Option<SignatureRecord>,
+}
+
+impl From<&Tag<'_>> for TagRecord {
+    fn from(tag: &Tag<'_>) -> Self {
+        Self {
+            name: lossy(tag.name()),
+            id: tag.id().to_string(),
+            message: tag.message().map(lossy),
+            tagger: tag.tagger().as_ref().map(SignatureRecord::from),
+        }
+    }
+}
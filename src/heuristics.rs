@@ -0,0 +1,125 @@
+use std::path::Path;
+
+use crate::FileCategory;
+
+/// Files written by package managers
+const LOCKFILES: [&str; 12] = [
+    "Cargo.lock",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "Gemfile.lock",
+    "composer.lock",
+    "poetry.lock",
+    "Pipfile.lock",
+    "go.sum",
+    "Podfile.lock",
+    "flake.lock",
+];
+
+/// Directories that hold copies of other projects
+const VENDOR_DIRECTORIES: [&str; 9] = [
+    "node_modules",
+    "bower_components",
+    "vendor",
+    "vendors",
+    "third_party",
+    "third-party",
+    "thirdparty",
+    "Pods",
+    "Carthage",
+];
+
+/// Comments that tools leave at the top of files they write, compared in lowercase with the
+/// start of the comment
+const GENERATED_MARKERS: [&str; 5] = [
+    "@generated",
+    "generated by",
+    "code generated by",
+    "auto-generated",
+    "autogenerated",
+];
+
+/// Prefixes of the comments [`GENERATED_MARKERS`] are searched for in, longest first
+///
+/// Doc comments such as `///` or `/**` keep a leftover `/` or `*`, so they never match
+const COMMENT_PREFIXES: [&str; 7] = ["<!--", "//", "/*", "--", "#", ";", "*"];
+
+/// Number of lines at the top of a file searched for one of the [`GENERATED_MARKERS`]
+const HEADER_LINES: usize = 5;
+
+/// Lines longer than this many bytes are only written by minifiers
+const MAX_LINE_LENGTH: usize = 1000;
+
+/// Number of bytes searched for a NUL byte, the same as git
+const BINARY_SEARCH_LENGTH: usize = 8000;
+
+/// Whether `content` is binary, using git's check for a NUL byte at the start of the file
+pub(crate) fn is_binary(content: &[u8]) -> bool {
+    content
+        .iter()
+        .take(BINARY_SEARCH_LENGTH)
+        .any(|byte| *byte == 0)
+}
+
+/// Guess the [`FileCategory`](enum.FileCategory.html) of a file without `.gitattributes`
+///
+/// Paths are checked for lockfiles, vendor directories, and `.min.` extensions, then `content`
+/// is checked for a generated header comment and extremely long lines
+pub(crate) fn detect(path: &Path, content: Option<&[u8]>) -> Option<FileCategory> {
+    let file_name = path.file_name()?.to_string_lossy();
+    if path
+        .parent()
+        .into_iter()
+        .flat_map(Path::iter)
+        .any(|directory| VENDOR_DIRECTORIES.iter().any(|vendor| directory == *vendor))
+    {
+        return Some(FileCategory::Vendored);
+    }
+    if LOCKFILES.contains(&file_name.as_ref()) {
+        return Some(FileCategory::Generated);
+    }
+    if file_name.contains(".min.") || file_name.contains("-min.") {
+        return Some(FileCategory::Minified);
+    }
+
+    let content = content?;
+    // Binary files have neither header comments nor lines
+    if is_binary(content) {
+        return None;
+    }
+    let generated = content
+        .split(|byte| *byte == b'\n')
+        .take(HEADER_LINES)
+        .map(|line| String::from_utf8_lossy(line).to_lowercase())
+        .any(|line| is_generated_header(&line));
+    if generated {
+        return Some(FileCategory::Generated);
+    }
+    if content
+        .split(|byte| *byte == b'\n')
+        .any(|line| line.len() > MAX_LINE_LENGTH)
+    {
+        return Some(FileCategory::Minified);
+    }
+    None
+}
+
+/// Whether a lowercase `line` is a comment starting with one of the [`GENERATED_MARKERS`],
+/// such as `// @generated` or `# Generated by Django`
+fn is_generated_header(line: &str) -> bool {
+    let line = line.trim_start();
+    match COMMENT_PREFIXES
+        .iter()
+        .find_map(|prefix| line.strip_prefix(prefix))
+    {
+        Some(comment) => {
+            let comment = comment.trim_start();
+            GENERATED_MARKERS
+                .iter()
+                .any(|marker| comment.starts_with(marker))
+        }
+        None => false,
+    }
+}
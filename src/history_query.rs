@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+use git2::Repository;
+
+use crate::Error;
+
+/// Selects which commits are walked when collecting history
+///
+/// Without any revisions `HEAD` and all of its parents are walked, the same as
+/// [`commits`](struct.GitDetective.html#method.commits)
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use chrono::{TimeZone, Utc};
+/// use git_detective::{GitDetective, HistoryQuery};
+///
+/// # fn main() -> Result<(), Error> {
+/// let gd = GitDetective::open(".")?;
+///
+/// let mut sprint = HistoryQuery::new();
+/// sprint
+///     .start("HEAD")
+///     .since(Utc.ymd(2020, 5, 1).and_hms(0, 0, 0))
+///     .until(Utc.ymd(2020, 5, 15).and_hms(0, 0, 0))
+///     .first_parent(true);
+///
+/// let diff_stats = gd.diff_stats_in(&sprint)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HistoryQuery {
+    ranges: Vec<String>,
+    starts: Vec<String>,
+    all: bool,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    first_parent: bool,
+}
+
+impl HistoryQuery {
+    /// Construct a new HistoryQuery that walks `HEAD`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Walk a revision range such as `v1.0..v2.0` or `main..feature`
+    ///
+    /// Commits reachable from the right side, but not from the left side are walked
+    pub fn range<S: Into<String>>(&mut self, range: S) -> &mut Self {
+        self.ranges.push(range.into());
+        self
+    }
+
+    /// Walk a revision and all of its parents, can be called multiple times
+    ///
+    /// Revisions prefixed with `^` are hidden along with all of their parents, the same as `git log`
+    pub fn start<S: Into<String>>(&mut self, revision: S) -> &mut Self {
+        self.starts.push(revision.into());
+        self
+    }
+
+    /// Walk all references, the same as `git log --all`
+    pub fn all(&mut self, all: bool) -> &mut Self {
+        self.all = all;
+        self
+    }
+
+    /// Only include commits made at or after `date`
+    pub fn since(&mut self, date: DateTime<Utc>) -> &mut Self {
+        self.since = Some(date);
+        self
+    }
+
+    /// Only include commits made at or before `date`
+    pub fn until(&mut self, date: DateTime<Utc>) -> &mut Self {
+        self.until = Some(date);
+        self
+    }
+
+    /// Only follow the first parent of merge commits, the same as `git log --first-parent`
+    pub fn first_parent(&mut self, first_parent: bool) -> &mut Self {
+        self.first_parent = first_parent;
+        self
+    }
+
+    /// Newest revision walked, the right side of the first range or the first start, otherwise `HEAD`
+    ///
+    /// Returns `None` when there is no `HEAD`
+    ///
+    /// # Errors
+    /// - Revision or range doesn't exist [`GitError`](enum.Error.html#variant.GitError)
+    pub(crate) fn tip<'repo>(
+        &self,
+        repository: &'repo Repository,
+    ) -> Result<Option<git2::Commit<'repo>>, Error> {
+        for range in self.ranges.iter() {
+            if let Some(to) = repository.revparse(range)?.to() {
+                return Ok(Some(to.peel_to_commit()?));
+            }
+        }
+        if let Some(start) = self.starts.iter().find(|start| !start.starts_with('^')) {
+            return Ok(Some(repository.revparse_single(start)?.peel_to_commit()?));
+        }
+        Ok(repository
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .ok())
+    }
+
+    /// Walk all commits selected by HistoryQuery
+    ///
+    /// # Errors
+    /// - Revision or range doesn't exist [`GitError`](enum.Error.html#variant.GitError)
+    pub(crate) fn walk<'repo>(
+        &self,
+        repository: &'repo Repository,
+    ) -> Result<impl Iterator<Item = git2::Commit<'repo>>, Error> {
+        let mut rev_walk = repository.revwalk()?;
+        let mut pushed = false;
+        for range in self.ranges.iter() {
+            rev_walk.push_range(range)?;
+            pushed = true;
+        }
+        for start in self.starts.iter() {
+            if let Some(hidden) = start.strip_prefix('^') {
+                rev_walk.hide(repository.revparse_single(hidden)?.peel_to_commit()?.id())?;
+            } else {
+                rev_walk.push(repository.revparse_single(start)?.peel_to_commit()?.id())?;
+                pushed = true;
+            }
+        }
+        if self.all {
+            rev_walk.push_glob("refs/*")?;
+        }
+        if self.all || !pushed {
+            rev_walk.push_head()?;
+        }
+        if self.first_parent {
+            rev_walk.simplify_first_parent()?;
+        }
+
+        let (since, until) = (self.since, self.until);
+        Ok(rev_walk
+            .flatten()
+            .filter_map(move |id| repository.find_commit(id).ok())
+            .filter(move |commit| {
+                let timestamp = commit.time().seconds();
+                let after_since = match since {
+                    Some(since) => timestamp >= since.timestamp(),
+                    None => true,
+                };
+                let before_until = match until {
+                    Some(until) => timestamp <= until.timestamp(),
+                    None => true,
+                };
+                after_since && before_until
+            }))
+    }
+}
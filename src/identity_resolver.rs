@@ -0,0 +1,153 @@
+use regex::Regex;
+
+use crate::mailmap::Mailmap;
+use crate::{Contributor, Error};
+
+/// How an identity is matched to a canonical [`Contributor`](struct.Contributor.html)
+#[derive(Debug, Clone)]
+pub enum IdentityMatcher {
+    /// Email, ignoring case
+    Email(String),
+    /// Exact name
+    Name(String),
+    /// Regular expression matched against both the name and the email
+    Regex(Regex),
+    /// Name, ignoring case and treating all runs of whitespace as a single space
+    Normalized(String),
+}
+
+impl IdentityMatcher {
+    /// Construct a [`Regex`](enum.IdentityMatcher.html#variant.Regex) matcher
+    ///
+    /// # Errors
+    /// - Pattern isn't a valid regular expression [`RegexError`](enum.Error.html#variant.RegexError)
+    pub fn regex<S: AsRef<str>>(pattern: S) -> Result<Self, Error> {
+        Ok(IdentityMatcher::Regex(Regex::new(pattern.as_ref())?))
+    }
+
+    /// Whether or not an identity is matched
+    fn is_match(&self, name: &str, email: &str) -> bool {
+        match self {
+            IdentityMatcher::Email(matcher) => matcher.eq_ignore_ascii_case(email),
+            IdentityMatcher::Name(matcher) => matcher == name,
+            IdentityMatcher::Regex(regex) => regex.is_match(name) || regex.is_match(email),
+            IdentityMatcher::Normalized(matcher) => normalize(matcher) == normalize(name),
+        }
+    }
+}
+
+/// Lowercase and collapse whitespace
+fn normalize(name: &str) -> String {
+    name.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Maps aliases of the same person to a canonical [`Contributor`](struct.Contributor.html)
+///
+/// Aliases are checked in the order they were added, after the repository's `.mailmap` has been applied.
+/// Identities that don't match any alias become a Contributor with their own name and email.
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::{Contributor, GitDetective, IdentityMatcher, IdentityResolver};
+///
+/// # fn main() -> Result<(), Error> {
+/// let john = Contributor::new("John Smith").with_email("smith.1@osu.edu");
+///
+/// let mut resolver = IdentityResolver::new();
+/// resolver
+///     .alias(IdentityMatcher::Email("jsmith@stdlinux.osu.edu".into()), john.clone())
+///     .alias(IdentityMatcher::Name("jsmith".into()), john.clone())
+///     .alias(IdentityMatcher::regex(r"^j\.?\s*smith$")?, john.clone())
+///     .alias(IdentityMatcher::Normalized("john  SMITH".into()), john);
+///
+/// let mut gd = GitDetective::open(".")?;
+/// gd.set_identity_resolver(resolver);
+///
+/// let contributors = gd.contributors()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IdentityResolver {
+    aliases: Vec<(IdentityMatcher, Contributor)>,
+}
+
+impl IdentityResolver {
+    /// Construct a new IdentityResolver without any aliases
+    pub fn new() -> Self {
+        Self {
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Map all identities matched by `matcher` to `contributor`
+    pub fn alias<C: Into<Contributor>>(
+        &mut self,
+        matcher: IdentityMatcher,
+        contributor: C,
+    ) -> &mut Self {
+        self.aliases.push((matcher, contributor.into()));
+        self
+    }
+
+    /// Resolve a name and email to a canonical [`Contributor`](struct.Contributor.html)
+    ///
+    /// The email is always added to the Contributor's known emails
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use git_detective::{Contributor, IdentityMatcher, IdentityResolver};
+    ///
+    /// let mut resolver = IdentityResolver::new();
+    /// resolver.alias(IdentityMatcher::Name("jsmith".into()), "John Smith");
+    ///
+    /// let contributor = resolver.resolve("jsmith", "jsmith@lab.local");
+    /// assert_eq!(contributor.name(), "John Smith");
+    /// assert!(contributor.emails().contains("jsmith@lab.local"));
+    /// ```
+    pub fn resolve(&self, name: &str, email: &str) -> Contributor {
+        self.aliases
+            .iter()
+            .find(|(matcher, _)| matcher.is_match(name, email))
+            .map_or_else(
+                || Contributor::new(name),
+                |(_, contributor)| contributor.clone(),
+            )
+            .with_email(email)
+    }
+}
+
+/// The `.mailmap` and [`IdentityResolver`](struct.IdentityResolver.html) of a
+/// [`GitDetective`](struct.GitDetective.html) used together
+pub(crate) struct Identities {
+    mailmap: Mailmap,
+    resolver: IdentityResolver,
+}
+
+impl Identities {
+    pub(crate) fn new(mailmap: Mailmap, resolver: IdentityResolver) -> Self {
+        Self { mailmap, resolver }
+    }
+
+    /// Canonical Contributor of a [`git2::Signature`](https://docs.rs/git2/latest/git2/struct.Signature.html)
+    ///
+    /// # Optional
+    /// - Name isn't valid UTF-8
+    pub(crate) fn resolve(&self, signature: &git2::Signature<'_>) -> Option<Contributor> {
+        let name = signature.name()?;
+        let email = signature.email().unwrap_or_default();
+        Some(self.resolve_identity(name, email))
+    }
+
+    /// Canonical Contributor of a name and email
+    pub(crate) fn resolve_identity(&self, name: &str, email: &str) -> Contributor {
+        let (name, email) = self.mailmap.resolve(name, email);
+        self.resolver.resolve(name, email)
+    }
+}
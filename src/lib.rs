@@ -7,7 +7,7 @@
 //! use git_detective::GitDetective;
 //!
 //! # fn main() -> Result<(), Error> {
-//! let mut gd = GitDetective::open(".")?;
+//! let gd = GitDetective::open(".")?;
 //!
 //! let project_stats = gd.final_contributions()?;
 //!
@@ -30,30 +30,100 @@
     unused_must_use
 )]
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+use chrono::NaiveDate;
+use ignore::gitignore::GitignoreBuilder;
 use rayon::prelude::*;
-use tokei::{Config, LanguageType};
+use tokei::{Config, LanguageType, LineType};
 use url::Url;
 
 pub(crate) mod git;
 use git::GitReference;
-pub use git::{Branch, Commit, FileStatus, Signature, Tag};
-use git2::{Repository, StatusOptions, StatusShow};
-pub use git2::{RepositoryState, Status};
+pub use git::{
+    Branch, Commit, CommitRecord, FileStatus, Signature, SignatureRecord, Tag, TagRecord,
+};
+use git2::{
+    Delta, DiffOptions, ObjectType, Patch, Repository, StatusOptions, StatusShow, TreeWalkMode,
+    TreeWalkResult,
+};
+pub use git2::{Oid, RepositoryState, Status};
 
 pub(crate) mod error;
 pub use error::Error;
 
+#[cfg(feature = "serde")]
+pub(crate) mod serialize;
+
 pub(crate) mod stats;
 pub use stats::Stats;
 
 pub(crate) mod project_stats;
 pub use project_stats::ProjectStats;
 
+pub(crate) mod binary_stats;
+pub use binary_stats::BinaryStats;
+
 pub(crate) mod diff_stats;
 pub use diff_stats::DiffStats;
+use diff_stats::FileDiffStats;
+
+pub(crate) mod mailmap;
+use mailmap::Mailmap;
+
+pub(crate) mod contributor;
+pub use contributor::Contributor;
+
+pub(crate) mod identity_resolver;
+use identity_resolver::Identities;
+pub use identity_resolver::{IdentityMatcher, IdentityResolver};
+
+pub(crate) mod credit_policy;
+pub use credit_policy::CreditPolicy;
+
+pub(crate) mod history_query;
+pub use history_query::HistoryQuery;
+
+pub(crate) mod exclusions;
+use exclusions::Exclusions;
+
+pub(crate) mod whitespace_mode;
+pub use whitespace_mode::WhitespaceMode;
+
+pub(crate) mod blame_settings;
+pub use blame_settings::BlameSettings;
+use blame_settings::BlamedLine;
+
+pub(crate) mod file_category;
+pub use file_category::{CategoryPolicy, FileCategory};
+
+pub(crate) mod classifier;
+use classifier::Classifier;
+
+pub(crate) mod heuristics;
+
+pub(crate) mod file_churn;
+pub use file_churn::FileChurn;
+
+pub(crate) mod merge_policy;
+pub use merge_policy::MergePolicy;
+
+pub(crate) mod rename_detection;
+pub use rename_detection::RenameDetection;
+use rename_detection::RenameHistory;
+
+pub(crate) mod period;
+pub use period::Period;
+
+pub(crate) mod activity;
+pub use activity::Activity;
+
+pub(crate) mod stats_delta;
+pub use stats_delta::StatsDelta;
+
+pub(crate) mod snapshot;
+pub use snapshot::Snapshot;
 
 /// Enables more in-depth investigating of Git Repositories
 ///
@@ -83,6 +153,16 @@ pub use diff_stats::DiffStats;
 pub struct GitDetective {
     repository: Repository,
     excluded_files: HashSet<String>,
+    exclusion_patterns: Vec<String>,
+    mailmap_files: Vec<PathBuf>,
+    identity_resolver: IdentityResolver,
+    credit_policy: CreditPolicy,
+    blame_settings: BlameSettings,
+    whitespace_mode: WhitespaceMode,
+    category_policy: CategoryPolicy,
+    heuristics: bool,
+    rename_detection: RenameDetection,
+    merge_policy: MergePolicy,
 }
 
 impl GitDetective {
@@ -103,6 +183,16 @@ impl GitDetective {
         Ok(Self {
             repository: Repository::discover(path)?,
             excluded_files: HashSet::new(),
+            exclusion_patterns: Vec::new(),
+            mailmap_files: Vec::new(),
+            identity_resolver: IdentityResolver::new(),
+            credit_policy: CreditPolicy::default(),
+            blame_settings: BlameSettings::default(),
+            whitespace_mode: WhitespaceMode::default(),
+            category_policy: CategoryPolicy::default(),
+            heuristics: false,
+            rename_detection: RenameDetection::default(),
+            merge_policy: MergePolicy::default(),
         })
     }
 
@@ -140,11 +230,26 @@ impl GitDetective {
         Ok(Self {
             repository,
             excluded_files: HashSet::new(),
+            exclusion_patterns: Vec::new(),
+            mailmap_files: Vec::new(),
+            identity_resolver: IdentityResolver::new(),
+            credit_policy: CreditPolicy::default(),
+            blame_settings: BlameSettings::default(),
+            whitespace_mode: WhitespaceMode::default(),
+            category_policy: CategoryPolicy::default(),
+            heuristics: false,
+            rename_detection: RenameDetection::default(),
+            merge_policy: MergePolicy::default(),
         })
     }
 
     /// `HashSet` of all contributors of Repository
     ///
+    /// Contributors are canonicalized using the repository's `.mailmap`, see
+    /// [`mailmap_file`](struct.GitDetective.html#method.mailmap_file) and
+    /// [`set_identity_resolver`](struct.GitDetective.html#method.set_identity_resolver).
+    /// Co-authors are included unless the [`CreditPolicy`](enum.CreditPolicy.html) is `AuthorOnly`
+    ///
     /// # Example
     ///
     /// ```
@@ -159,20 +264,85 @@ impl GitDetective {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn contributors(&self) -> Result<HashSet<String>, Error> {
-        let mut rev_walk = self.repository.revwalk()?;
-        rev_walk.push_head()?;
-        Ok(rev_walk
-            .flatten()
-            .filter_map(|id| self.repository.find_commit(id).ok())
+    pub fn contributors(&self) -> Result<HashSet<Contributor>, Error> {
+        self.contributors_in(&HistoryQuery::default())
+    }
+
+    /// `HashSet` of all contributors to the commits selected by a [`HistoryQuery`](struct.HistoryQuery.html)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{GitDetective, HistoryQuery};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let repo = GitDetective::open(".")?;
+    /// let mut query = HistoryQuery::new();
+    /// query.all(true);
+    ///
+    /// let contributors = repo.contributors_in(&query)?;
+    /// assert!(contributors.contains("Nick Hackman"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// - Revision or range in `query` doesn't exist [`GitError`](enum.Error.html#variant.GitError)
+    pub fn contributors_in(&self, query: &HistoryQuery) -> Result<HashSet<Contributor>, Error> {
+        let identities = self.identities()?;
+        Ok(query
+            .walk(&self.repository)?
             .fold(HashSet::new(), |mut set, commit| {
-                if let Some(name) = commit.author().name() {
-                    set.insert(name.to_string());
+                for contributor in self.credited(&identities, &commit) {
+                    contributor::insert(&mut set, contributor);
                 }
                 set
             }))
     }
 
+    /// Number of commits credited to every contributor in commits that are parents of `HEAD`
+    ///
+    /// Contributors are canonicalized the same as [`contributors`](struct.GitDetective.html#method.contributors),
+    /// and co-authors are credited with the commit according to the [`CreditPolicy`](enum.CreditPolicy.html)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::GitDetective;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let repo = GitDetective::open(".")?;
+    /// for (contributor, commits) in repo.commit_counts()? {
+    ///   println!("{}: {} commits", contributor, commits);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn commit_counts(&self) -> Result<HashMap<Contributor, usize>, Error> {
+        self.commit_counts_in(&HistoryQuery::default())
+    }
+
+    /// Number of commits credited to every contributor in the commits selected by a [`HistoryQuery`](struct.HistoryQuery.html)
+    ///
+    /// # Errors
+    /// - Revision or range in `query` doesn't exist [`GitError`](enum.Error.html#variant.GitError)
+    pub fn commit_counts_in(
+        &self,
+        query: &HistoryQuery,
+    ) -> Result<HashMap<Contributor, usize>, Error> {
+        let identities = self.identities()?;
+        Ok(query
+            .walk(&self.repository)?
+            .fold(HashMap::new(), |mut counts, commit| {
+                for contributor in self.credited(&identities, &commit) {
+                    *contributor::entry(&mut counts, contributor) += 1;
+                }
+                counts
+            }))
+    }
+
     /// All tags of Repository
     ///
     /// # Example
@@ -240,11 +410,34 @@ impl GitDetective {
     /// # }
     /// ```
     pub fn commits(&self) -> Result<impl Iterator<Item = Commit<'_>>, Error> {
-        let mut rev_walk = self.repository.revwalk()?;
-        rev_walk.push_head()?;
-        Ok(rev_walk
-            .flatten()
-            .filter_map(move |id| self.repository.find_commit(id).map(Commit::from).ok()))
+        self.commits_in(&HistoryQuery::default())
+    }
+
+    /// All commits selected by a [`HistoryQuery`](struct.HistoryQuery.html) in **reverse** order
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{GitDetective, HistoryQuery};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let repo = GitDetective::open(".")?;
+    /// let mut query = HistoryQuery::new();
+    /// query.start("HEAD").start("^HEAD~2");
+    ///
+    /// assert!(repo.commits_in(&query)?.count() <= 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// - Revision or range in `query` doesn't exist [`GitError`](enum.Error.html#variant.GitError)
+    pub fn commits_in(
+        &self,
+        query: &HistoryQuery,
+    ) -> Result<impl Iterator<Item = Commit<'_>>, Error> {
+        Ok(query.walk(&self.repository)?.map(Commit::from))
     }
 
     /// Current state of Repository
@@ -274,12 +467,14 @@ impl GitDetective {
     /// and the repository should **NOT** be edited unless `HEAD` is reattached.
     ///
     /// # Errors
+    /// - Repository is bare [`BareRepository`](enum.Error.html#variant.BareRepository)
     /// - [`RepositoryState`]() is not `RepositoryState::Clean`
     /// - `GitReference` doesn't exist in the repository
     pub fn checkout<'repo, GitRef: GitReference<'repo>>(
         &self,
         git_ref: GitRef,
     ) -> Result<(), Error> {
+        self.workdir()?;
         let state = self.state();
         if state != RepositoryState::Clean {
             return Err(Error::UncleanState(state));
@@ -293,6 +488,9 @@ impl GitDetective {
 
     /// List files in the Index
     ///
+    /// Bare repositories don't have an index, so every file in the tree of `HEAD` is
+    /// listed as [`Status::CURRENT`](struct.Status.html#associatedconstant.CURRENT)
+    ///
     /// # Example
     ///
     /// ```
@@ -313,6 +511,18 @@ impl GitDetective {
     /// # Errors
     /// - Couldn't read Git Repository
     pub fn ls(&self) -> Result<Vec<FileStatus>, Error> {
+        if self.repository.is_bare() {
+            let tree = self.repository.head()?.peel_to_tree()?;
+            return Ok(self
+                .ls_tree(&tree)?
+                .into_iter()
+                .map(|(path, _)| FileStatus {
+                    path: path.to_string_lossy().to_string(),
+                    status: Status::CURRENT,
+                })
+                .collect());
+        }
+        let exclusions = self.exclusions()?;
         let mut base_options = StatusOptions::new();
         let options = base_options
             .show(StatusShow::IndexAndWorkdir)
@@ -322,25 +532,199 @@ impl GitDetective {
             .statuses(Some(options))?
             .iter()
             .map(FileStatus::from)
-            .filter(|file_stat| !self.excluded_files.contains(&file_stat.path))
+            .filter(|file_stat| !exclusions.is_excluded(&file_stat.path))
             .collect())
     }
 
+    /// All exact and pattern exclusions
+    ///
+    /// Patterns in `.gitdetectiveignore`, from the working directory or `HEAD`, come before
+    /// patterns added with [`exclude`](struct.GitDetective.html#method.exclude)
+    fn exclusions(&self) -> Result<Exclusions, Error> {
+        let workdir_ignore = self
+            .repository
+            .workdir()
+            .map(|workdir| workdir.join(".gitdetectiveignore"))
+            .filter(|path| path.is_file());
+        let ignore_file = if let Some(path) = workdir_ignore {
+            std::fs::read_to_string(&path).map_err(|err| Error::IOError(err, path))?
+        } else if let Ok(object) = self.repository.revparse_single("HEAD:.gitdetectiveignore") {
+            match object.as_blob() {
+                Some(blob) => String::from_utf8_lossy(blob.content()).to_string(),
+                None => String::new(),
+            }
+        } else {
+            String::new()
+        };
+        let patterns = ignore_file
+            .lines()
+            .chain(self.exclusion_patterns.iter().map(String::as_str));
+        Exclusions::new(self.excluded_files.clone(), patterns)
+    }
+
+    /// Internal Function
+    ///
+    /// [`Classifier`] for the `.gitattributes` of `HEAD`, every file is source code when there is no `HEAD`
+    ///
+    /// # Errors
+    /// - Pattern in `.gitattributes` isn't a valid glob [`IgnoreError`](enum.Error.html#variant.IgnoreError)
+    fn head_classifier(&self) -> Result<Classifier, Error> {
+        let tree = self.repository.head().and_then(|head| head.peel_to_tree());
+        self.classifier(tree.as_ref().ok())
+    }
+
+    /// Internal Function
+    ///
+    /// [`Classifier`] for the `.gitattributes` of the newest revision walked by `query`, such as `v2.0`
+    /// in `v1.0..v2.0`, every file is source code when there is no such revision
+    ///
+    /// # Errors
+    /// - Revision or range in `query` doesn't exist [`GitError`](enum.Error.html#variant.GitError)
+    /// - Pattern in `.gitattributes` isn't a valid glob [`IgnoreError`](enum.Error.html#variant.IgnoreError)
+    fn query_classifier(&self, query: &HistoryQuery) -> Result<Classifier, Error> {
+        match query.tip(&self.repository)? {
+            Some(tip) => self.classifier(Some(&tip.tree()?)),
+            None => self.classifier(None),
+        }
+    }
+
+    /// Internal Function
+    ///
+    /// [`Classifier`] for the `.gitattributes` of `tree` that guesses categories when heuristics are enabled
+    ///
+    /// # Errors
+    /// - Pattern in `.gitattributes` isn't a valid glob [`IgnoreError`](enum.Error.html#variant.IgnoreError)
+    fn classifier(&self, tree: Option<&git2::Tree<'_>>) -> Result<Classifier, Error> {
+        let classifier = match tree {
+            Some(tree) => Classifier::from_tree(&self.repository, tree)?,
+            None => Classifier::empty(),
+        };
+        Ok(classifier.heuristics(self.heuristics))
+    }
+
     /// Get workdir
-    fn workdir(&self) -> PathBuf {
-        // Safe to unwrap because we don't allow bare repositories
-        self.repository.workdir().unwrap().into()
+    ///
+    /// # Errors
+    /// - Repository is bare [`BareRepository`](enum.Error.html#variant.BareRepository)
+    fn workdir(&self) -> Result<PathBuf, Error> {
+        self.repository
+            .workdir()
+            .map(PathBuf::from)
+            .ok_or(Error::BareRepository)
+    }
+
+    /// Build the `Mailmap` used to canonicalize contributors
+    ///
+    /// Reads the repository's `.mailmap` from the working directory, then the blob named by
+    /// `mailmap.blob` from git config, or `HEAD:.mailmap` when neither exists, followed by
+    /// `mailmap.file` from git config and all files added with
+    /// [`mailmap_file`](struct.GitDetective.html#method.mailmap_file)
+    ///
+    /// # Errors
+    /// - `mailmap.blob` names an object that isn't a blob [`GitError`](enum.Error.html#variant.GitError)
+    fn mailmap(&self) -> Result<Mailmap, Error> {
+        let mut mailmap = Mailmap::default();
+        let config = self.repository.config()?;
+        let workdir_mailmap = self
+            .repository
+            .workdir()
+            .map(|workdir| workdir.join(".mailmap"))
+            .filter(|path| path.is_file());
+        let mailmap_blob = match config.get_string("mailmap.blob") {
+            Ok(spec) => Some(spec),
+            Err(_) if workdir_mailmap.is_none() => Some(String::from("HEAD:.mailmap")),
+            Err(_) => None,
+        };
+        if let Some(path) = workdir_mailmap {
+            mailmap.add_file(path)?;
+        }
+        // Same as git, a `mailmap.blob` that doesn't resolve is skipped
+        if let Some(Ok(object)) = mailmap_blob.map(|spec| self.repository.revparse_single(&spec)) {
+            mailmap.add_buffer(String::from_utf8_lossy(object.peel_to_blob()?.content()));
+        }
+        if let Ok(path) = config.get_path("mailmap.file") {
+            mailmap.add_file(path)?;
+        }
+        for path in self.mailmap_files.iter() {
+            mailmap.add_file(path)?;
+        }
+        Ok(mailmap)
+    }
+
+    /// Combine the `Mailmap` with the [`IdentityResolver`](struct.IdentityResolver.html)
+    fn identities(&self) -> Result<Identities, Error> {
+        Ok(Identities::new(
+            self.mailmap()?,
+            self.identity_resolver.clone(),
+        ))
+    }
+
+    /// Contributors credited for a commit according to the [`CreditPolicy`](enum.CreditPolicy.html)
+    ///
+    /// The author is always first, co-authors that resolve to the author are only credited once
+    fn credited(&self, identities: &Identities, commit: &git2::Commit<'_>) -> Vec<Contributor> {
+        let mut credited: Vec<Contributor> =
+            identities.resolve(&commit.author()).into_iter().collect();
+        if self.credit_policy == CreditPolicy::AuthorOnly {
+            return credited;
+        }
+        for (name, email) in git::commit::co_authors(commit.message_raw_bytes()) {
+            let co_author = identities.resolve_identity(&name, &email);
+            match credited
+                .iter_mut()
+                .find(|contributor| **contributor == co_author)
+            {
+                Some(contributor) => contributor.merge(co_author),
+                None => credited.push(co_author),
+            }
+        }
+        credited
+    }
+
+    /// [`BlameSettings`](struct.BlameSettings.html) including the repository's ignored revisions
+    ///
+    /// Reads `.git-blame-ignore-revs` from the working directory or `HEAD`, then `blame.ignoreRevsFile`
+    fn blame_settings(&self) -> Result<BlameSettings, Error> {
+        let mut settings = self.blame_settings.clone();
+        settings.ignore_whitespace = self.whitespace_mode == WhitespaceMode::Insensitive;
+        let workdir_ignore_revs = self
+            .repository
+            .workdir()
+            .map(|workdir| workdir.join(".git-blame-ignore-revs"))
+            .filter(|path| path.is_file());
+        if let Some(path) = workdir_ignore_revs {
+            settings.ignore_revisions_file(path)?;
+        } else if let Ok(object) = self
+            .repository
+            .revparse_single("HEAD:.git-blame-ignore-revs")
+        {
+            if let Some(blob) = object.as_blob() {
+                settings.ignore_revisions_buffer(&String::from_utf8_lossy(blob.content()))?;
+            }
+        }
+        if let Ok(path) = self.repository.config()?.get_path("blame.ignoreRevsFile") {
+            let path = match self.repository.workdir() {
+                Some(workdir) => workdir.join(path),
+                None => path,
+            };
+            settings.ignore_revisions_file(path)?;
+        }
+        Ok(settings)
     }
 
     /// Get the blame for a file
-    fn blame_file<P: AsRef<Path>>(&self, path: P) -> Result<git2::Blame<'_>, Error> {
-        Ok(self.repository.blame_file(&path.as_ref(), None)?)
+    fn blame_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<BlamedLine>, Error> {
+        self.blame_settings()?
+            .blame(&self.repository, path.as_ref(), None)
     }
 
     /// Count the final contibutions for an entire git repository
     ///
     /// Final contributions takes the last commit, and completely
     /// ignores current untracked changes in the git repository.
+    /// Bare repositories are counted the same as [`final_contributions_at("HEAD")`](struct.GitDetective.html#method.final_contributions_at).
+    ///
+    /// Files are blamed in parallel, each job opens its own handle to the repository.
     ///
     /// # Example
     ///
@@ -349,7 +733,7 @@ impl GitDetective {
     /// use git_detective::GitDetective;
     ///
     /// # fn main() -> Result<(), Error> {
-    /// let mut gd = GitDetective::open(".")?;
+    /// let gd = GitDetective::open(".")?;
     /// let project_stats = gd.final_contributions()?;
     ///
     /// for contributor in project_stats.contributors() {
@@ -369,34 +753,20 @@ impl GitDetective {
     /// # Errors
     /// - Failed to read file [`IOError`](enum.Error.html#variant.IOError)
     /// - Failed to git blame [`GitError`](enum.Error.html#variant.GitError)
-    pub fn final_contributions(&mut self) -> Result<ProjectStats, Error> {
-        let files = self.ls()?;
-        let workdir = self.workdir();
-        let repo = std::sync::Mutex::new(self);
-        Ok(files
-            .par_iter()
-            .filter_map(|file| {
-                if let Ok(repo) = repo.lock() {
-                    if let Ok(blame) = repo.blame_file(&file.path) {
-                        return GitDetective::_final_contributions_file(
-                            &workdir, &file.path, blame,
-                        )
-                        .map(ProjectStats::from)
-                        .ok();
-                    }
-                }
-                None
-            })
-            .reduce(ProjectStats::default, |mut stats_lhs, stats_rhs| {
-                stats_lhs += stats_rhs;
-                stats_lhs
-            }))
+    pub fn final_contributions(&self) -> Result<ProjectStats, Error> {
+        let mut project_stats = ProjectStats::default();
+        for file_stats in self.final_contributions_by_file()?.into_values() {
+            project_stats += file_stats;
+        }
+        project_stats.set_whitespace_mode(self.whitespace_mode);
+        Ok(project_stats)
     }
 
-    /// Count the final contibutions for a file
+    /// Count the final contributions of every file in the git repository
     ///
-    /// Final contributions takes the last commit, and completely
-    /// ignores current untracked changes in the git repository.
+    /// Files are blamed once, the same as [`final_contributions`](struct.GitDetective.html#method.final_contributions),
+    /// which adds up every file. Files dropped by the [`CategoryPolicy`](enum.CategoryPolicy.html), binary files,
+    /// and files that aren't committed or were deleted from the working directory are left out.
     ///
     /// # Example
     ///
@@ -406,80 +776,77 @@ impl GitDetective {
     ///
     /// # fn main() -> Result<(), Error> {
     /// let gd = GitDetective::open(".")?;
-    /// let (lang, final_contribs) = gd.final_contributions_file(file!())?;
-    ///
-    /// println!("Language = {}", lang);
-    /// println!("final contributions = {:?}", final_contribs);
-    ///
+    /// for (path, project_stats) in gd.final_contributions_by_file()? {
+    ///   println!("{}: {} lines", path.display(), project_stats.total_lines());
+    /// }
     /// # Ok(())
     /// # }
     /// ```
     ///
-    /// # All of my `code` is "Plain Text"
-    ///
-    /// `Git-Detective` fallsback to interpreting files as "Plain Text" when no file type can be determined.
-    /// This is common for files like `Cargo.lock` and `LICENSE`.
-    ///
     /// # Errors
     /// - Failed to read file [`IOError`](enum.Error.html#variant.IOError)
     /// - Failed to git blame [`GitError`](enum.Error.html#variant.GitError)
-    pub fn final_contributions_file<P: AsRef<Path>>(
-        &self,
-        path: P,
-    ) -> Result<(&'static str, HashMap<String, Stats>), Error> {
-        let path = path.as_ref();
-        let blame = self.blame_file(path)?;
-        let workdir = self.workdir();
-        GitDetective::_final_contributions_file(&workdir, path, blame)
-    }
-
-    /// Internal Function
-    ///
-    /// Performs final contributions counting for a file
-    fn _final_contributions_file<Dir: Into<PathBuf>, P: AsRef<Path>>(
-        workdir: Dir,
-        path: P,
-        blame: git2::Blame<'_>,
-    ) -> Result<(&'static str, HashMap<String, Stats>), Error> {
-        let workdir = workdir.into();
-        let path = path.as_ref();
-        let full_path = workdir.join(path);
-        let config = Config::default();
-
-        let lang_type = LanguageType::from_path(&full_path, &config).unwrap_or(LanguageType::Text);
-        let annotations = lang_type
-            .annotate_file(full_path, &config)
-            .map_err(|(err, path)| Error::IOError(err, path))?;
-
-        let contributions = blame
-            .iter()
-            .fold(HashMap::new(), |mut contributions, hunk| {
-                let final_sig = hunk.final_signature();
-                let final_author = match final_sig.name() {
-                    Some(name) => name.to_string(),
-                    // TODO: Log Non-UTF8 name, instead of silently ignoring
-                    None => return contributions,
-                };
-
-                let end = hunk.final_start_line() + hunk.lines_in_hunk();
-                for line_num in hunk.final_start_line()..end {
-                    let line_type = match annotations.get(&line_num) {
-                        Some(line_type) => line_type,
-                        None => continue,
+    /// - Failed to open the repository for a parallel job [`GitError`](enum.Error.html#variant.GitError)
+    pub fn final_contributions_by_file(&self) -> Result<HashMap<PathBuf, ProjectStats>, Error> {
+        if self.repository.is_bare() {
+            return self.final_contributions_by_file_at("HEAD");
+        }
+        let files = self.ls()?;
+        let workdir = self.workdir()?;
+        let identities = self.identities()?;
+        let repository_path = self.repository.path();
+        let blame_settings = &self.blame_settings()?;
+        let classifier = &self.head_classifier()?;
+        let category_policy = self.category_policy;
+        let whitespace_mode = self.whitespace_mode;
+        files
+            .par_iter()
+            .map_init(
+                // Rayon runs `init` once for every job it splits the files into, not once per thread
+                || Repository::open(repository_path),
+                |repository, file| -> Result<Option<(PathBuf, ProjectStats)>, Error> {
+                    let repository = GitDetective::job_repository(repository)?;
+                    // Files that aren't committed, or no longer exist in the working directory,
+                    // have nothing to count
+                    if file.status.intersects(Status::INDEX_NEW | Status::WT_NEW) {
+                        return Ok(None);
+                    }
+                    let full_path = workdir.join(&file.path);
+                    if !full_path.is_file() {
+                        return Ok(None);
+                    }
+                    let content = std::fs::read(&full_path)
+                        .map_err(|err| Error::IOError(err, full_path.clone()))?;
+                    // Binary files have no lines to count
+                    if heuristics::is_binary(&content) {
+                        return Ok(None);
+                    }
+                    let category = classifier.category(&file.path, Some(content.as_slice()));
+                    let category = match category_policy.bucket(category) {
+                        Some(category) => category,
+                        None => return Ok(None),
                     };
-                    let stats = contributions
-                        .entry(final_author.clone())
-                        .or_insert_with(Stats::default);
-                    *stats += line_type;
-                }
-                contributions
-            });
-
-        Ok((lang_type.name(), contributions))
+                    let path = Path::new(&file.path);
+                    let blame = blame_settings.blame(repository, path, None)?;
+                    let stats = GitDetective::_final_contributions_blob(
+                        path,
+                        &content,
+                        &blame,
+                        &identities,
+                    );
+                    let mut file_stats = ProjectStats::from(stats).into_category(category);
+                    file_stats.set_whitespace_mode(whitespace_mode);
+                    Ok(Some((PathBuf::from(&file.path), file_stats)))
+                },
+            )
+            .filter_map(Result::transpose)
+            .collect()
     }
 
-    /// Exclude a file from all further [`ls`](struct.GitDetective.html#method.ls) and
-    /// [`final_contributions`](struct.GitDetective.html#method.final_contributions)
+    /// Count the final contibutions for an entire git repository at any revision
+    ///
+    /// Files are read from the tree of `revision` and blamed up to it, so neither the
+    /// working directory nor `HEAD` are touched, this works on repositories with uncommitted changes.
     ///
     /// # Example
     ///
@@ -488,24 +855,99 @@ impl GitDetective {
     /// use git_detective::GitDetective;
     ///
     /// # fn main() -> Result<(), Error> {
-    /// let mut gd = GitDetective::open(".")?;
-    /// let before_files = gd.ls()?;
-    ///
-    /// gd.exclude_file("README.md");
-    /// gd.exclude_file("Cargo.toml");
+    /// let gd = GitDetective::open(".")?;
+    /// let previous = gd.final_contributions_at("HEAD~1")?;
+    /// let current = gd.final_contributions_at("HEAD")?;
     ///
-    /// let mut after_files = gd.ls()?;
-    /// assert!(after_files.iter().all(|file| &file.path != "README.md" && &file.path != "Cargo.toml"));
+    /// println!("{} -> {}", previous.total_lines(), current.total_lines());
     /// # Ok(())
     /// # }
     /// ```
-    pub fn exclude_file<S: Into<String>>(&mut self, file: S) {
-        self.excluded_files.insert(file.into());
+    ///
+    /// # Errors
+    /// - `revision` doesn't exist or doesn't point to a commit [`GitError`](enum.Error.html#variant.GitError)
+    /// - Failed to read the tree of `revision` [`GitError`](enum.Error.html#variant.GitError)
+    /// - Failed to git blame [`GitError`](enum.Error.html#variant.GitError)
+    pub fn final_contributions_at<S: AsRef<str>>(
+        &self,
+        revision: S,
+    ) -> Result<ProjectStats, Error> {
+        let mut project_stats = ProjectStats::default();
+        for file_stats in self.final_contributions_by_file_at(revision)?.into_values() {
+            project_stats += file_stats;
+        }
+        project_stats.set_whitespace_mode(self.whitespace_mode);
+        Ok(project_stats)
     }
 
-    /// Get insertion/deletion statistics
+    /// Internal Function
     ///
-    /// The same `+` and `-` deltas that Github shows in the [contributors](https://github.com/NickHackman/Git-Detective/graphs/contributors) page
+    /// Final contributions of every file in the tree of `revision`, blamed up to `revision`
+    ///
+    /// # Errors
+    /// - `revision` doesn't exist or doesn't point to a commit [`GitError`](enum.Error.html#variant.GitError)
+    /// - Failed to read the tree of `revision` [`GitError`](enum.Error.html#variant.GitError)
+    /// - Failed to git blame [`GitError`](enum.Error.html#variant.GitError)
+    fn final_contributions_by_file_at<S: AsRef<str>>(
+        &self,
+        revision: S,
+    ) -> Result<HashMap<PathBuf, ProjectStats>, Error> {
+        let (commit_id, files, classifier) = {
+            let commit = self
+                .repository
+                .revparse_single(revision.as_ref())?
+                .peel_to_commit()?;
+            let tree = commit.tree()?;
+            (
+                commit.id(),
+                self.ls_tree(&tree)?,
+                self.classifier(Some(&tree))?,
+            )
+        };
+        let identities = self.identities()?;
+        let repository_path = self.repository.path();
+        let blame_settings = &self.blame_settings()?;
+        let category_policy = self.category_policy;
+        let whitespace_mode = self.whitespace_mode;
+        files
+            .par_iter()
+            .map_init(
+                // Rayon runs `init` once for every job it splits the files into, not once per thread
+                || Repository::open(repository_path),
+                |repository, (path, blob_id)| -> Result<Option<(PathBuf, ProjectStats)>, Error> {
+                    let repository = GitDetective::job_repository(repository)?;
+                    let blob = repository.find_blob(*blob_id)?;
+                    // Binary files have no lines to count
+                    if heuristics::is_binary(blob.content()) {
+                        return Ok(None);
+                    }
+                    let category = classifier.category(path, Some(blob.content()));
+                    let category = match category_policy.bucket(category) {
+                        Some(category) => category,
+                        None => return Ok(None),
+                    };
+                    let blame = blame_settings.blame(repository, path, Some(commit_id))?;
+                    let stats = GitDetective::_final_contributions_blob(
+                        path,
+                        blob.content(),
+                        &blame,
+                        &identities,
+                    );
+                    let mut file_stats = ProjectStats::from(stats).into_category(category);
+                    file_stats.set_whitespace_mode(whitespace_mode);
+                    Ok(Some((path.to_path_buf(), file_stats)))
+                },
+            )
+            .filter_map(Result::transpose)
+            .collect()
+    }
+
+    /// Count the final contributions at every tag, both annotated and lightweight
+    ///
+    /// Snapshots are ordered by the date of the tagged commit, so `iteration-10` comes after `iteration-9`,
+    /// nothing is checked out the same as [`final_contributions_at`](struct.GitDetective.html#method.final_contributions_at).
+    /// Tags are resolved as `refs/tags/<name>`, so a branch with the same name doesn't matter, and tags
+    /// that don't point to a commit are skipped
     ///
     /// # Example
     ///
@@ -514,47 +956,35 @@ impl GitDetective {
     /// use git_detective::GitDetective;
     ///
     /// # fn main() -> Result<(), Error> {
-    /// let mut gd = GitDetective::open(".")?;
-    /// let diff_stats = gd.diff_stats()?;
-    /// for (author, diff_stat) in diff_stats {
-    ///   println!("{}: +{} -{}", author, diff_stat.insertions, diff_stat.deletions);
+    /// let gd = GitDetective::open(".")?;
+    /// for snapshot in gd.snapshots()? {
+    ///   for (contributor, delta) in snapshot.delta {
+    ///     println!("{} {}: {:+} lines", snapshot.revision, contributor, delta.lines);
+    ///   }
     /// }
-    ///
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
-    ///
-    /// - Unable to walk commits
-    /// - Unable to get [`git2::Tree`](https://docs.rs/git2/latest/git2/struct.Tree.html) for a [`git2::Commit`](https://docs.rs/git2/latest/git2/struct.Commit.html)
-    /// - Unable to get the stats for a [`git2::Diff`](https://docs.rs/git2/latest/git2/struct.Diff.html)
-    pub fn diff_stats(&self) -> Result<HashMap<String, DiffStats>, Error> {
-        let mut rev_walk = self.repository.revwalk()?;
-        rev_walk.push_head()?;
-        Ok(rev_walk
-            .flatten()
-            .filter_map(|id| self.repository.find_commit(id).ok())
-            .try_fold(HashMap::new(), |mut contribs, commit| -> Result<_, Error> {
-                let old_tree = commit
-                    .parent(0)
-                    .map_or(None, |parent| parent.tree().map_or(None, Some));
-                let new_tree = commit.tree()?;
-                let diff =
-                    self.repository
-                        .diff_tree_to_tree(old_tree.as_ref(), Some(&new_tree), None)?;
-                if let Some(author) = commit.author().name() {
-                    let author = author.into();
-                    let entry = contribs.entry(author).or_insert_with(DiffStats::default);
-                    *entry += diff.stats()?;
-                }
-                Ok(contribs)
-            })?)
+    /// - Unable to list tags [`GitError`](enum.Error.html#variant.GitError)
+    /// - Failed to read the tree of a tag [`GitError`](enum.Error.html#variant.GitError)
+    pub fn snapshots(&self) -> Result<Vec<Snapshot>, Error> {
+        let mut tags = Vec::new();
+        for name in self.repository.tag_names(None)?.iter().flatten() {
+            let reference = self
+                .repository
+                .find_reference(&format!("refs/tags/{}", name))?;
+            if let Ok(commit) = reference.peel_to_commit() {
+                tags.push((name.to_string(), commit));
+            }
+        }
+        // Tags of the same commit stay sorted by name
+        tags.sort_by_key(|(_, commit)| commit.time().seconds());
+        self.snapshots_of(tags)
     }
 
-    /// Get files contributed to by all Contributors in commits that are parents of `HEAD`
-    ///
-    /// All file paths are given relatively to the Git working directory
+    /// Count the final contributions at every revision in `revisions`, in the order given
     ///
     /// # Example
     ///
@@ -563,48 +993,1170 @@ impl GitDetective {
     /// use git_detective::GitDetective;
     ///
     /// # fn main() -> Result<(), Error> {
-    /// let mut gd = GitDetective::open(".")?;
-    /// let contrib_files = gd.files_contributed_to()?;
-    /// for (author, files) in contrib_files {
-    ///   println!("{}: {:?}", author, files);
-    /// }
-    ///
+    /// let gd = GitDetective::open(".")?;
+    /// let snapshots = gd.snapshots_at(["HEAD~1", "HEAD"])?;
+    /// assert_eq!(snapshots.len(), 2);
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
+    /// - A revision doesn't exist or doesn't point to a commit [`GitError`](enum.Error.html#variant.GitError)
+    /// - Failed to read the tree of a revision [`GitError`](enum.Error.html#variant.GitError)
+    pub fn snapshots_at<I, S>(&self, revisions: I) -> Result<Vec<Snapshot>, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut commits = Vec::new();
+        for revision in revisions {
+            let revision = revision.as_ref();
+            let commit = self
+                .repository
+                .revparse_single(revision)?
+                .peel_to_commit()?;
+            commits.push((revision.to_string(), commit));
+        }
+        self.snapshots_of(commits)
+    }
+
+    /// Internal Function
     ///
-    /// - Unable to walk commits
-    /// - Unable to get [`git2::Tree`](https://docs.rs/git2/latest/git2/struct.Tree.html) for a [`git2::Commit`](https://docs.rs/git2/latest/git2/struct.Commit.html)
-    /// - Unable to get the stats for a [`git2::Diff`](https://docs.rs/git2/latest/git2/struct.Diff.html)
-    pub fn files_contributed_to(&self) -> Result<HashMap<String, HashSet<PathBuf>>, Error> {
-        let mut rev_walk = self.repository.revwalk()?;
-        rev_walk.push_head()?;
-        Ok(rev_walk
-            .flatten()
-            .filter_map(|id| self.repository.find_commit(id).ok())
-            .try_fold(HashMap::new(), |mut contribs, commit| -> Result<_, Error> {
-                let old_tree = commit
-                    .parent(0)
-                    .map_or(None, |parent| parent.tree().map_or(None, Some));
-                let new_tree = commit.tree()?;
-                let diff =
-                    self.repository
-                        .diff_tree_to_tree(old_tree.as_ref(), Some(&new_tree), None)?;
-
-                if let Some(author) = commit.author().name() {
-                    let author = author.into();
-                    let files = diff.deltas().fold(HashSet::new(), |mut files, delta| {
-                        if let Some(path) = delta.new_file().path() {
-                            files.insert(path.to_path_buf());
-                        }
-                        files
-                    });
-                    let prev_files = contribs.entry(author).or_insert_with(HashSet::default);
+    /// Snapshot every already resolved revision, in the order given
+    ///
+    /// # Errors
+    /// - Failed to read the tree of a revision [`GitError`](enum.Error.html#variant.GitError)
+    fn snapshots_of(
+        &self,
+        commits: Vec<(String, git2::Commit<'_>)>,
+    ) -> Result<Vec<Snapshot>, Error> {
+        let mut snapshots: Vec<Snapshot> = Vec::new();
+        for (revision, commit) in commits {
+            let stats = self.final_contributions_at(commit.id().to_string())?;
+            let previous = snapshots.last().map(|snapshot| &snapshot.stats);
+            let delta = snapshot::delta(previous, &stats);
+            let categories = snapshot::category_delta(previous, &stats);
+            snapshots.push(Snapshot {
+                revision,
+                id: commit.id(),
+                date: Commit::from(commit).date(),
+                stats,
+                delta,
+                categories,
+            });
+        }
+        Ok(snapshots)
+    }
+
+    /// List the path and [`Oid`](https://docs.rs/git2/latest/git2/struct.Oid.html) of every blob in a tree
+    ///
+    /// Excluded files are skipped the same as [`ls`](struct.GitDetective.html#method.ls)
+    fn ls_tree(&self, tree: &git2::Tree<'_>) -> Result<Vec<(PathBuf, Oid)>, Error> {
+        let exclusions = self.exclusions()?;
+        let mut files = Vec::new();
+        tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+            if entry.kind() == Some(ObjectType::Blob) {
+                let path = format!("{}{}", root, String::from_utf8_lossy(entry.name_bytes()));
+                if !exclusions.is_excluded(&path) {
+                    files.push((PathBuf::from(path), entry.id()));
+                }
+            }
+            TreeWalkResult::Ok
+        })?;
+        Ok(files)
+    }
+
+    /// Count the final contibutions for a file
+    ///
+    /// Final contributions takes the last commit, and completely
+    /// ignores current untracked changes in the git repository.
+    /// In bare repositories the file is read from `HEAD`.
+    /// Binary files have no lines, so no one is credited.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::GitDetective;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let gd = GitDetective::open(".")?;
+    /// let (lang, final_contribs) = gd.final_contributions_file(file!())?;
+    ///
+    /// println!("Language = {}", lang);
+    /// println!("final contributions = {:?}", final_contribs);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # All of my `code` is "Plain Text"
+    ///
+    /// `Git-Detective` fallsback to interpreting files as "Plain Text" when no file type can be determined.
+    /// This is common for files like `Cargo.lock` and `LICENSE`.
+    ///
+    /// # Errors
+    /// - Failed to read file [`IOError`](enum.Error.html#variant.IOError)
+    /// - Failed to git blame [`GitError`](enum.Error.html#variant.GitError)
+    pub fn final_contributions_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<(&'static str, HashMap<Contributor, Stats>), Error> {
+        let path = path.as_ref();
+        let blame = self.blame_file(path)?;
+        let identities = self.identities()?;
+        if self.repository.is_bare() {
+            let blob = self
+                .repository
+                .head()?
+                .peel_to_tree()?
+                .get_path(path)?
+                .to_object(&self.repository)?
+                .peel_to_blob()?;
+            return Ok(GitDetective::_final_contributions_blob(
+                path,
+                blob.content(),
+                &blame,
+                &identities,
+            ));
+        }
+        let workdir = self.workdir()?;
+        GitDetective::_final_contributions_file(&workdir, path, &blame, &identities)
+    }
+
+    /// Internal Function
+    ///
+    /// Repository handle opened for a parallel job, failing to open it fails the whole count
+    /// instead of silently skipping every file in the job
+    ///
+    /// # Errors
+    /// - Failed to open the repository [`GitError`](enum.Error.html#variant.GitError)
+    fn job_repository(repository: &Result<Repository, git2::Error>) -> Result<&Repository, Error> {
+        repository.as_ref().map_err(|err| {
+            Error::GitError(git2::Error::new(err.code(), err.class(), err.message()))
+        })
+    }
+
+    /// Internal Function
+    ///
+    /// Performs final contributions counting for a file
+    fn _final_contributions_file<Dir: Into<PathBuf>, P: AsRef<Path>>(
+        workdir: Dir,
+        path: P,
+        blame: &[BlamedLine],
+        identities: &Identities,
+    ) -> Result<(&'static str, HashMap<Contributor, Stats>), Error> {
+        let workdir = workdir.into();
+        let path = path.as_ref();
+        let full_path = workdir.join(path);
+        let config = Config::default();
+
+        let lang_type = LanguageType::from_path(&full_path, &config).unwrap_or(LanguageType::Text);
+        let content =
+            std::fs::read(&full_path).map_err(|err| Error::IOError(err, full_path.clone()))?;
+        if heuristics::is_binary(&content) {
+            return Ok((lang_type.name(), HashMap::new()));
+        }
+        let annotations = lang_type.annotate_from_slice(&content, &config);
+
+        let contributions =
+            GitDetective::attribute_lines(blame, identities, |line| annotations.get(&line));
+        Ok((lang_type.name(), contributions))
+    }
+
+    /// Internal Function
+    ///
+    /// Performs final contributions counting for the contents of a blob, without reading the working directory
+    fn _final_contributions_blob<P: AsRef<Path>>(
+        path: P,
+        content: &[u8],
+        blame: &[BlamedLine],
+        identities: &Identities,
+    ) -> (&'static str, HashMap<Contributor, Stats>) {
+        let config = Config::default();
+        let lang_type = LanguageType::from_path(path, &config).unwrap_or(LanguageType::Text);
+        if heuristics::is_binary(content) {
+            return (lang_type.name(), HashMap::new());
+        }
+        let annotations = lang_type.annotate_from_slice(content, &config);
+
+        let contributions =
+            GitDetective::attribute_lines(blame, identities, |line| annotations.get(&line));
+        (lang_type.name(), contributions)
+    }
+
+    /// Internal Function
+    ///
+    /// Credits the final author of each line in `blame` with the type of that line
+    fn attribute_lines<'a, F>(
+        blame: &[BlamedLine],
+        identities: &Identities,
+        line_type_of: F,
+    ) -> HashMap<Contributor, Stats>
+    where
+        F: Fn(usize) -> Option<&'a LineType>,
+    {
+        let mut authors: HashMap<Oid, Option<Contributor>> = HashMap::new();
+        let mut contributions = HashMap::new();
+        for (index, line) in blame.iter().enumerate() {
+            let final_author = authors
+                .entry(line.commit)
+                .or_insert_with(|| identities.resolve(&line.signature));
+            let final_author = match final_author {
+                Some(contributor) => contributor,
+                // TODO: Log Non-UTF8 name, instead of silently ignoring
+                None => continue,
+            };
+            // Line numbers start at 1
+            let line_type = match line_type_of(index + 1) {
+                Some(line_type) => line_type,
+                None => continue,
+            };
+            let stats = contributor::entry(&mut contributions, final_author.clone());
+            *stats += line_type;
+        }
+        contributions
+    }
+
+    /// Exclude a file from all further [`ls`](struct.GitDetective.html#method.ls),
+    /// [`final_contributions`](struct.GitDetective.html#method.final_contributions),
+    /// [`diff_stats`](struct.GitDetective.html#method.diff_stats), and
+    /// [`files_contributed_to`](struct.GitDetective.html#method.files_contributed_to)
+    ///
+    /// The path must match exactly, see [`exclude`](struct.GitDetective.html#method.exclude) for patterns
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::GitDetective;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut gd = GitDetective::open(".")?;
+    /// let before_files = gd.ls()?;
+    ///
+    /// gd.exclude_file("README.md");
+    /// gd.exclude_file("Cargo.toml");
+    ///
+    /// let mut after_files = gd.ls()?;
+    /// assert!(after_files.iter().all(|file| &file.path != "README.md" && &file.path != "Cargo.toml"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn exclude_file<S: Into<String>>(&mut self, file: S) {
+        self.excluded_files.insert(file.into());
+    }
+
+    /// Exclude all files matching a [gitignore](https://git-scm.com/docs/gitignore) pattern from all further
+    /// [`ls`](struct.GitDetective.html#method.ls),
+    /// [`final_contributions`](struct.GitDetective.html#method.final_contributions),
+    /// [`diff_stats`](struct.GitDetective.html#method.diff_stats), and
+    /// [`files_contributed_to`](struct.GitDetective.html#method.files_contributed_to)
+    ///
+    /// Patterns are applied after those in the repository's `.gitdetectiveignore`, patterns added
+    /// later take precedence and patterns starting with `!` include files again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::GitDetective;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut gd = GitDetective::open(".")?;
+    /// gd.exclude("*.md")?;
+    /// gd.exclude("vendor/**")?;
+    /// gd.exclude("Cargo.lock")?;
+    /// gd.include("README.md")?;
+    ///
+    /// let files = gd.ls()?;
+    /// assert!(files.iter().all(|file| &file.path != "Cargo.lock"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// - Pattern isn't a valid glob [`IgnoreError`](enum.Error.html#variant.IgnoreError)
+    pub fn exclude<S: Into<String>>(&mut self, pattern: S) -> Result<(), Error> {
+        let pattern = pattern.into();
+        GitignoreBuilder::new(".").add_line(None, &pattern)?;
+        self.exclusion_patterns.push(pattern);
+        Ok(())
+    }
+
+    /// Include files matching a [gitignore](https://git-scm.com/docs/gitignore) pattern that were excluded
+    /// by earlier patterns, the same as [`exclude`](struct.GitDetective.html#method.exclude) with a leading `!`
+    ///
+    /// # Errors
+    /// - Pattern isn't a valid glob [`IgnoreError`](enum.Error.html#variant.IgnoreError)
+    pub fn include<S: AsRef<str>>(&mut self, pattern: S) -> Result<(), Error> {
+        self.exclude(format!("!{}", pattern.as_ref()))
+    }
+
+    /// Resolve aliases of the same person to a canonical [`Contributor`](struct.Contributor.html)
+    ///
+    /// The `IdentityResolver` is consulted after `.mailmap` for every contributor, including
+    /// [`ProjectStats`](struct.ProjectStats.html), [`diff_stats`](struct.GitDetective.html#method.diff_stats), and
+    /// [`files_contributed_to`](struct.GitDetective.html#method.files_contributed_to)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{GitDetective, IdentityMatcher, IdentityResolver};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut resolver = IdentityResolver::new();
+    /// resolver.alias(IdentityMatcher::Name("NickHackman".into()), "Nick Hackman");
+    ///
+    /// let mut gd = GitDetective::open(".")?;
+    /// gd.set_identity_resolver(resolver);
+    ///
+    /// let diff_stats = gd.diff_stats()?;
+    /// assert!(!diff_stats.contains_key("NickHackman"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_identity_resolver(&mut self, resolver: IdentityResolver) {
+        self.identity_resolver = resolver;
+    }
+
+    /// Choose how co-authors from `Co-authored-by:` trailers are credited, see [`CreditPolicy`](enum.CreditPolicy.html)
+    ///
+    /// Defaults to `CreditPolicy::AuthorOnly`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{CreditPolicy, GitDetective};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut gd = GitDetective::open(".")?;
+    /// gd.set_credit_policy(CreditPolicy::FullCredit);
+    ///
+    /// let contrib_files = gd.files_contributed_to()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_credit_policy(&mut self, policy: CreditPolicy) {
+        self.credit_policy = policy;
+    }
+
+    /// Use an additional [mailmap](https://git-scm.com/docs/gitmailmap) file when canonicalizing contributors
+    ///
+    /// The repository's own `.mailmap`, `mailmap.blob`, and `mailmap.file` are always used,
+    /// entries in files added here take precedence over them.
+    /// Applies to [`contributors`](struct.GitDetective.html#method.contributors),
+    /// [`final_contributions`](struct.GitDetective.html#method.final_contributions),
+    /// [`diff_stats`](struct.GitDetective.html#method.diff_stats), and
+    /// [`files_contributed_to`](struct.GitDetective.html#method.files_contributed_to)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use git_detective::Error;
+    /// use git_detective::GitDetective;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut gd = GitDetective::open(".")?;
+    /// gd.mailmap_file("course.mailmap");
+    ///
+    /// let contributors = gd.contributors()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn mailmap_file<P: Into<PathBuf>>(&mut self, path: P) {
+        self.mailmap_files.push(path.into());
+    }
+
+    /// Choose which moved, copied, and ignored lines are credited to their original author,
+    /// see [`BlameSettings`](struct.BlameSettings.html)
+    ///
+    /// Applies to [`final_contributions`](struct.GitDetective.html#method.final_contributions),
+    /// [`final_contributions_at`](struct.GitDetective.html#method.final_contributions_at), and
+    /// [`final_contributions_file`](struct.GitDetective.html#method.final_contributions_file)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{BlameSettings, GitDetective};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut settings = BlameSettings::new();
+    /// settings.track_copies_same_commit(true);
+    ///
+    /// let mut gd = GitDetective::open(".")?;
+    /// gd.set_blame_settings(settings);
+    ///
+    /// let (lang, final_contribs) = gd.final_contributions_file(file!())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_blame_settings(&mut self, settings: BlameSettings) {
+        self.blame_settings = settings;
+    }
+
+    /// Choose whether whitespace-only changes are credited, see [`WhitespaceMode`](enum.WhitespaceMode.html)
+    ///
+    /// Defaults to `WhitespaceMode::Sensitive`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{GitDetective, WhitespaceMode};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut gd = GitDetective::open(".")?;
+    /// gd.set_whitespace_mode(WhitespaceMode::Insensitive);
+    ///
+    /// for (author, diff_stat) in gd.diff_stats()? {
+    ///   assert_eq!(diff_stat.whitespace_mode, WhitespaceMode::Insensitive);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_whitespace_mode(&mut self, mode: WhitespaceMode) {
+        self.whitespace_mode = mode;
+    }
+
+    /// Choose what happens to generated, vendored, documentation, and binary files, see [`CategoryPolicy`](enum.CategoryPolicy.html)
+    ///
+    /// Defaults to `CategoryPolicy::Exclude`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{CategoryPolicy, GitDetective};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut gd = GitDetective::open(".")?;
+    /// gd.set_category_policy(CategoryPolicy::Include);
+    ///
+    /// let project_stats = gd.final_contributions()?;
+    /// assert_eq!(project_stats.categories().count(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_category_policy(&mut self, policy: CategoryPolicy) {
+        self.category_policy = policy;
+    }
+
+    /// Guess the [`FileCategory`](enum.FileCategory.html) of files without `.gitattributes`
+    ///
+    /// Lockfiles, files in vendor directories such as `node_modules`, files starting with a generated
+    /// header comment such as `// @generated`, and minified files are categorized, then handled with the
+    /// [`CategoryPolicy`](enum.CategoryPolicy.html)
+    ///
+    /// Defaults to `false`, only `.gitattributes` categorize files
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{CategoryPolicy, GitDetective};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut gd = GitDetective::open(".")?;
+    /// gd.set_heuristics(true);
+    /// gd.set_category_policy(CategoryPolicy::Separate);
+    ///
+    /// let project_stats = gd.final_contributions()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_heuristics(&mut self, heuristics: bool) {
+        self.heuristics = heuristics;
+    }
+
+    /// Choose how renamed and copied files are detected, see [`RenameDetection`](struct.RenameDetection.html)
+    ///
+    /// Applies to [`diff_stats`](struct.GitDetective.html#method.diff_stats),
+    /// [`files_contributed_to`](struct.GitDetective.html#method.files_contributed_to), and
+    /// [`file_churn`](struct.GitDetective.html#method.file_churn)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{GitDetective, RenameDetection};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut detection = RenameDetection::new();
+    /// detection.renames(false).copies(false);
+    ///
+    /// let mut gd = GitDetective::open(".")?;
+    /// gd.set_rename_detection(detection);
+    ///
+    /// let diff_stats = gd.diff_stats()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_rename_detection(&mut self, detection: RenameDetection) {
+        self.rename_detection = detection;
+    }
+
+    /// Choose how changes in merge commits are credited, see [`MergePolicy`](enum.MergePolicy.html)
+    ///
+    /// Defaults to `MergePolicy::Skip`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{GitDetective, MergePolicy};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut gd = GitDetective::open(".")?;
+    /// gd.set_merge_policy(MergePolicy::FirstParent);
+    ///
+    /// let contrib_files = gd.files_contributed_to()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_merge_policy(&mut self, policy: MergePolicy) {
+        self.merge_policy = policy;
+    }
+
+    /// Get insertion/deletion statistics
+    ///
+    /// The same `+` and `-` deltas that Github shows in the [contributors](https://github.com/NickHackman/Git-Detective/graphs/contributors) page
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::GitDetective;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut gd = GitDetective::open(".")?;
+    /// let diff_stats = gd.diff_stats()?;
+    /// for (author, diff_stat) in diff_stats {
+    ///   println!("{}: +{} -{}", author, diff_stat.insertions, diff_stat.deletions);
+    /// }
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - Unable to walk commits
+    /// - Unable to get [`git2::Tree`](https://docs.rs/git2/latest/git2/struct.Tree.html) for a [`git2::Commit`](https://docs.rs/git2/latest/git2/struct.Commit.html)
+    /// - Unable to get the stats for a [`git2::Diff`](https://docs.rs/git2/latest/git2/struct.Diff.html)
+    pub fn diff_stats(&self) -> Result<HashMap<Contributor, DiffStats>, Error> {
+        self.diff_stats_in(&HistoryQuery::default())
+    }
+
+    /// Get insertion/deletion statistics for the commits selected by a [`HistoryQuery`](struct.HistoryQuery.html)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{GitDetective, HistoryQuery};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let gd = GitDetective::open(".")?;
+    /// let mut milestone = HistoryQuery::new();
+    /// milestone.range("HEAD~1..HEAD");
+    ///
+    /// let diff_stats = gd.diff_stats_in(&milestone)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - Revision or range in `query` doesn't exist [`GitError`](enum.Error.html#variant.GitError)
+    /// - Unable to get [`git2::Tree`](https://docs.rs/git2/latest/git2/struct.Tree.html) for a [`git2::Commit`](https://docs.rs/git2/latest/git2/struct.Commit.html)
+    /// - Unable to get the stats for a [`git2::Diff`](https://docs.rs/git2/latest/git2/struct.Diff.html)
+    pub fn diff_stats_in(
+        &self,
+        query: &HistoryQuery,
+    ) -> Result<HashMap<Contributor, DiffStats>, Error> {
+        let identities = self.identities()?;
+        let exclusions = self.exclusions()?;
+        let classifier = self.query_classifier(query)?;
+        query.walk(&self.repository)?.try_fold(
+            HashMap::new(),
+            |mut contribs, commit| -> Result<_, Error> {
+                let stats = self.commit_diff_stats(&commit, &exclusions, &classifier)?;
+                let credited = self.credited(&identities, &commit);
+                for (contributor, share) in self.shares(stats, credited) {
+                    *contributor::entry(&mut contribs, contributor) += share;
+                }
+                Ok(contribs)
+            },
+        )
+    }
+
+    /// Internal Function
+    ///
+    /// Insertions and deletions of a whole commit, files outside of [`FileCategory::Source`](enum.FileCategory.html#variant.Source)
+    /// are counted in their category
+    fn commit_diff_stats(
+        &self,
+        commit: &git2::Commit<'_>,
+        exclusions: &Exclusions,
+        classifier: &Classifier,
+    ) -> Result<DiffStats, Error> {
+        let diffs = self.commit_diffs(commit)?;
+        let mut stats = DiffStats {
+            merges: usize::from(commit.parent_count() > 1),
+            ..DiffStats::default()
+        };
+        for file in self.file_diff_stats(&diffs, exclusions, classifier)? {
+            match file.category {
+                FileCategory::Source => stats += file.stats,
+                category => *stats.categories.entry(category).or_default() += file.stats,
+            }
+        }
+        stats.set_whitespace_mode(self.whitespace_mode);
+        Ok(stats)
+    }
+
+    /// Internal Function
+    ///
+    /// Diffs of a commit against the parents selected by the [`MergePolicy`](enum.MergePolicy.html)
+    ///
+    /// Root commits are diffed against nothing, merges are diffed against no parents, their first parent,
+    /// or every parent
+    fn commit_diffs(&self, commit: &git2::Commit<'_>) -> Result<Vec<git2::Diff<'_>>, Error> {
+        let old_trees = match (commit.parent_count(), self.merge_policy) {
+            (0, _) => vec![None],
+            (1, _) | (_, MergePolicy::FirstParent) => {
+                vec![commit.parent(0).and_then(|parent| parent.tree()).ok()]
+            }
+            (_, MergePolicy::Skip) => Vec::new(),
+            (_, MergePolicy::ConflictResolution) => {
+                commit.parents().map(|parent| parent.tree().ok()).collect()
+            }
+        };
+        let new_tree = commit.tree()?;
+        old_trees
+            .iter()
+            .map(|old_tree| self.diff_trees(old_tree.as_ref(), &new_tree))
+            .collect()
+    }
+
+    /// Internal Function
+    ///
+    /// Diff two trees, renamed and copied files are detected with the [`RenameDetection`](struct.RenameDetection.html)
+    fn diff_trees(
+        &self,
+        old_tree: Option<&git2::Tree<'_>>,
+        new_tree: &git2::Tree<'_>,
+    ) -> Result<git2::Diff<'_>, Error> {
+        let ignore_whitespace = self.whitespace_mode == WhitespaceMode::Insensitive;
+        let mut options = DiffOptions::new();
+        options.ignore_whitespace(ignore_whitespace);
+        let mut diff =
+            self.repository
+                .diff_tree_to_tree(old_tree, Some(new_tree), Some(&mut options))?;
+        self.rename_detection
+            .find_similar(&mut diff, ignore_whitespace)?;
+        Ok(diff)
+    }
+
+    /// Internal Function
+    ///
+    /// Divide `stats` between everyone `credited` with a commit according to the [`CreditPolicy`](enum.CreditPolicy.html)
+    fn shares(
+        &self,
+        stats: DiffStats,
+        credited: Vec<Contributor>,
+    ) -> Vec<(Contributor, DiffStats)> {
+        let shares = match self.credit_policy {
+            CreditPolicy::SplitEvenly => stats.split(credited.len()),
+            _ => vec![stats; credited.len()],
+        };
+        credited.into_iter().zip(shares).collect()
+    }
+
+    /// Internal Function
+    ///
+    /// Insertions and deletions of every file that isn't excluded in the diffs of a commit
+    ///
+    /// Merges diffed against several parents only count lines that changed against every parent.
+    /// Files are categorized by the `.gitattributes` of `classifier`, or guessed from their contents in the commit,
+    /// and handled with the [`CategoryPolicy`](enum.CategoryPolicy.html), except for binary files which are always counted
+    fn file_diff_stats(
+        &self,
+        diffs: &[git2::Diff<'_>],
+        exclusions: &Exclusions,
+        classifier: &Classifier,
+    ) -> Result<Vec<FileDiffStats>, Error> {
+        let config = Config::default();
+        let mut files = Vec::new();
+        let (diff, others) = match diffs.split_first() {
+            Some(split) => split,
+            None => return Ok(files),
+        };
+        let other_indices: Vec<HashMap<&Path, usize>> = others
+            .iter()
+            .map(|other| {
+                other
+                    .deltas()
+                    .enumerate()
+                    .filter_map(|(index, delta)| Some((delta.new_file().path()?, index)))
+                    .collect()
+            })
+            .collect();
+        let find_blob = |file: git2::DiffFile<'_>| {
+            if file.id().is_zero() {
+                None
+            } else {
+                self.repository.find_blob(file.id()).ok()
+            }
+        };
+        for (index, delta) in diff.deltas().enumerate() {
+            let path = match delta.new_file().path().or_else(|| delta.old_file().path()) {
+                Some(path) if !exclusions.is_excluded(path) => path,
+                _ => continue,
+            };
+            // Files that are the same as in any parent weren't changed by the merge
+            let other_deltas: Option<Vec<usize>> = other_indices
+                .iter()
+                .map(|indices| indices.get(path).copied())
+                .collect();
+            let other_deltas = match other_deltas {
+                Some(other_deltas) => other_deltas,
+                None => continue,
+            };
+            let lang_type = LanguageType::from_path(path, &config).unwrap_or(LanguageType::Text);
+            let old_blob = find_blob(delta.old_file());
+            let new_blob = find_blob(delta.new_file());
+            let old_content = old_blob.as_ref().map(|blob| blob.content());
+            let new_content = new_blob.as_ref().map(|blob| blob.content());
+            let renamed = others.is_empty() && delta.status() == Delta::Renamed;
+            let copied = others.is_empty() && delta.status() == Delta::Copied;
+            let renamed_from = if renamed {
+                delta.old_file().path().map(Path::to_path_buf)
+            } else {
+                None
+            };
+            let patch = Patch::from_diff(diff, index)?;
+            let binary = matches!(&patch, Some(patch) if patch.delta().flags().is_binary())
+                || matches!(new_content.or(old_content), Some(content) if heuristics::is_binary(content));
+            // Binary files are counted whatever their category, which is `Binary` when they're
+            // marked `binary` or `-diff`
+            if binary {
+                // Deleted binary files have nothing to credit
+                let bytes = match new_blob.as_ref() {
+                    Some(blob) => blob.size(),
+                    None => continue,
+                };
+                let mut stats = DiffStats {
+                    binary: BinaryStats {
+                        added: usize::from(delta.status() == Delta::Added),
+                        modified: usize::from(delta.status() != Delta::Added),
+                        bytes,
+                    },
+                    ..DiffStats::default()
+                };
+                stats.set_whitespace_mode(self.whitespace_mode);
+                files.push(FileDiffStats {
+                    path: path.to_path_buf(),
+                    renamed_from,
+                    category: FileCategory::Source,
+                    stats,
+                });
+                continue;
+            }
+            let category = classifier.category(path, new_content.or(old_content));
+            let category = match self.category_policy.bucket(category) {
+                Some(category) => category,
+                None => continue,
+            };
+            let (mut inserted_lines, mut deleted_lines) = match patch {
+                Some(patch) => GitDetective::changed_lines(&patch)?,
+                // Unchanged renames and copies are still counted
+                None if renamed || copied => Default::default(),
+                None => continue,
+            };
+            let old_lines: Vec<&[u8]> = match old_content {
+                Some(content) if !others.is_empty() => {
+                    content.split(|byte| *byte == b'\n').collect()
+                }
+                _ => Vec::new(),
+            };
+            for (other, other_index) in others.iter().zip(other_deltas) {
+                let patch = match Patch::from_diff(other, other_index)? {
+                    Some(patch) => patch,
+                    None => continue,
+                };
+                let (other_inserted, other_deleted) = GitDetective::changed_lines(&patch)?;
+                let other_inserted: HashSet<usize> = other_inserted.into_iter().collect();
+                inserted_lines.retain(|line| other_inserted.contains(line));
+                // Deleted lines are matched by content, their line numbers differ in each parent
+                let other_blob = other
+                    .get_delta(other_index)
+                    .and_then(|delta| find_blob(delta.old_file()));
+                let other_lines: Vec<&[u8]> = match other_blob.as_ref() {
+                    Some(blob) => blob.content().split(|byte| *byte == b'\n').collect(),
+                    None => Vec::new(),
+                };
+                let mut other_deleted_lines: HashMap<&[u8], usize> = HashMap::new();
+                for line in other_deleted {
+                    if let Some(content) = other_lines.get(line - 1) {
+                        *other_deleted_lines.entry(content).or_default() += 1;
+                    }
+                }
+                deleted_lines.retain(|line| {
+                    match old_lines
+                        .get(line - 1)
+                        .and_then(|content| other_deleted_lines.get_mut(content))
+                    {
+                        Some(count) if *count > 0 => {
+                            *count -= 1;
+                            true
+                        }
+                        _ => false,
+                    }
+                });
+            }
+            let (inserted, deleted) = GitDetective::classify_lines(
+                lang_type,
+                old_content,
+                new_content,
+                &inserted_lines,
+                &deleted_lines,
+                &config,
+            );
+            let mut stats = DiffStats::default();
+            stats.add_lines(lang_type.name(), inserted, deleted);
+            stats.renames = usize::from(renamed);
+            stats.copies = usize::from(copied);
+            stats.set_whitespace_mode(self.whitespace_mode);
+            files.push(FileDiffStats {
+                path: path.to_path_buf(),
+                renamed_from,
+                category,
+                stats,
+            });
+        }
+        Ok(files)
+    }
+
+    /// Internal Function
+    ///
+    /// Line numbers inserted into the new file and deleted from the old file by `patch`, starting at 1
+    fn changed_lines(patch: &Patch<'_>) -> Result<(Vec<usize>, Vec<usize>), Error> {
+        let (mut inserted, mut deleted) = (Vec::new(), Vec::new());
+        for hunk in 0..patch.num_hunks() {
+            for line in 0..patch.num_lines_in_hunk(hunk)? {
+                let line = patch.line_in_hunk(hunk, line)?;
+                match (line.origin(), line.old_lineno(), line.new_lineno()) {
+                    ('+', _, Some(lineno)) => inserted.push(lineno as usize),
+                    ('-', Some(lineno), _) => deleted.push(lineno as usize),
+                    _ => {}
+                }
+            }
+        }
+        Ok((inserted, deleted))
+    }
+
+    /// Internal Function
+    ///
+    /// Count the type of every inserted and deleted line
+    ///
+    /// The old and new contents are annotated as a whole, so lines inside block comments are comments
+    fn classify_lines(
+        lang_type: LanguageType,
+        old_content: Option<&[u8]>,
+        new_content: Option<&[u8]>,
+        inserted_lines: &[usize],
+        deleted_lines: &[usize],
+        config: &Config,
+    ) -> (Stats, Stats) {
+        let annotate = |content: Option<&[u8]>| match content {
+            Some(content) => lang_type.annotate_from_slice(content, config),
+            None => Default::default(),
+        };
+        let old_annotations = annotate(old_content);
+        let new_annotations = annotate(new_content);
+        let (mut inserted, mut deleted) = (Stats::default(), Stats::default());
+        // Lines tokei didn't annotate are counted as code
+        for line in inserted_lines {
+            inserted += new_annotations.get(line).unwrap_or(&LineType::Code);
+        }
+        for line in deleted_lines {
+            deleted += old_annotations.get(line).unwrap_or(&LineType::Code);
+        }
+        (inserted, deleted)
+    }
+
+    /// Get files contributed to by all Contributors in commits that are parents of `HEAD`
+    ///
+    /// All file paths are given relatively to the Git working directory
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::GitDetective;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let mut gd = GitDetective::open(".")?;
+    /// let contrib_files = gd.files_contributed_to()?;
+    /// for (author, files) in contrib_files {
+    ///   println!("{}: {:?}", author, files);
+    /// }
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - Unable to walk commits
+    /// - Unable to get [`git2::Tree`](https://docs.rs/git2/latest/git2/struct.Tree.html) for a [`git2::Commit`](https://docs.rs/git2/latest/git2/struct.Commit.html)
+    /// - Unable to get the stats for a [`git2::Diff`](https://docs.rs/git2/latest/git2/struct.Diff.html)
+    pub fn files_contributed_to(&self) -> Result<HashMap<Contributor, HashSet<PathBuf>>, Error> {
+        self.files_contributed_to_in(&HistoryQuery::default())
+    }
+
+    /// Get files contributed to by all Contributors in the commits selected by a [`HistoryQuery`](struct.HistoryQuery.html)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{GitDetective, HistoryQuery};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let gd = GitDetective::open(".")?;
+    /// let mut query = HistoryQuery::new();
+    /// query.start("HEAD").first_parent(true);
+    ///
+    /// let contrib_files = gd.files_contributed_to_in(&query)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - Revision or range in `query` doesn't exist [`GitError`](enum.Error.html#variant.GitError)
+    /// - Unable to get [`git2::Tree`](https://docs.rs/git2/latest/git2/struct.Tree.html) for a [`git2::Commit`](https://docs.rs/git2/latest/git2/struct.Commit.html)
+    pub fn files_contributed_to_in(
+        &self,
+        query: &HistoryQuery,
+    ) -> Result<HashMap<Contributor, HashSet<PathBuf>>, Error> {
+        let identities = self.identities()?;
+        let exclusions = self.exclusions()?;
+        let mut renames = RenameHistory::default();
+        query.walk(&self.repository)?.try_fold(
+            HashMap::new(),
+            |mut contribs, commit| -> Result<_, Error> {
+                let diffs = self.commit_diffs(&commit)?;
+                let (diff, others) = match diffs.split_first() {
+                    Some(split) => split,
+                    None => return Ok(contribs),
+                };
+
+                let mut files = HashSet::new();
+                for delta in diff.deltas() {
+                    if let Some(path) = delta.new_file().path() {
+                        if exclusions.is_excluded(path) {
+                            continue;
+                        }
+                        // Files that are the same as in any parent weren't changed by the merge
+                        let unchanged = others.iter().any(|other| {
+                            !other
+                                .deltas()
+                                .any(|other_delta| other_delta.new_file().path() == Some(path))
+                        });
+                        if unchanged {
+                            continue;
+                        }
+                        if let (Delta::Renamed, Some(old_path)) =
+                            (delta.status(), delta.old_file().path())
+                        {
+                            renames.renamed(old_path, path);
+                        }
+                        files.insert(renames.newest_path(path));
+                    }
+                }
+                for contributor in self.credited(&identities, &commit) {
+                    let prev_files = contributor::entry(&mut contribs, contributor);
                     *prev_files = files.union(prev_files).cloned().collect();
                 }
                 Ok(contribs)
-            })?)
+            },
+        )
+    }
+
+    /// Get how often and by whom every file was changed in commits that are parents of `HEAD`
+    ///
+    /// All file paths are given relatively to the Git working directory, renamed files are counted under their new path
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::GitDetective;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let gd = GitDetective::open(".")?;
+    /// let mut churn: Vec<_> = gd.file_churn()?.into_iter().collect();
+    /// churn.sort_by_key(|(_, churn)| std::cmp::Reverse(churn.commits));
+    /// for (path, churn) in churn.iter().take(10) {
+    ///   println!("{}: {} commits", path.display(), churn.commits);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - Unable to walk commits
+    /// - Unable to get [`git2::Tree`](https://docs.rs/git2/latest/git2/struct.Tree.html) for a [`git2::Commit`](https://docs.rs/git2/latest/git2/struct.Commit.html)
+    /// - Unable to get the stats for a [`git2::Diff`](https://docs.rs/git2/latest/git2/struct.Diff.html)
+    pub fn file_churn(&self) -> Result<HashMap<PathBuf, FileChurn>, Error> {
+        self.file_churn_in(&HistoryQuery::default())
+    }
+
+    /// Get how often and by whom every file was changed in the commits selected by a [`HistoryQuery`](struct.HistoryQuery.html)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{GitDetective, HistoryQuery};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let gd = GitDetective::open(".")?;
+    /// let mut query = HistoryQuery::new();
+    /// query.range("HEAD~1..HEAD");
+    ///
+    /// let churn = gd.file_churn_in(&query)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - Revision or range in `query` doesn't exist [`GitError`](enum.Error.html#variant.GitError)
+    /// - Unable to get [`git2::Tree`](https://docs.rs/git2/latest/git2/struct.Tree.html) for a [`git2::Commit`](https://docs.rs/git2/latest/git2/struct.Commit.html)
+    /// - Unable to get the stats for a [`git2::Diff`](https://docs.rs/git2/latest/git2/struct.Diff.html)
+    pub fn file_churn_in(
+        &self,
+        query: &HistoryQuery,
+    ) -> Result<HashMap<PathBuf, FileChurn>, Error> {
+        let identities = self.identities()?;
+        let exclusions = self.exclusions()?;
+        let classifier = self.query_classifier(query)?;
+        let mut renames = RenameHistory::default();
+        query.walk(&self.repository)?.try_fold(
+            HashMap::new(),
+            |mut churn: HashMap<PathBuf, FileChurn>, commit| -> Result<_, Error> {
+                let diffs = self.commit_diffs(&commit)?;
+                let credited = self.credited(&identities, &commit);
+                for file in self.file_diff_stats(&diffs, &exclusions, &classifier)? {
+                    if let Some(renamed_from) = file.renamed_from.as_ref() {
+                        renames.renamed(renamed_from, &file.path);
+                    }
+                    let file_churn = churn.entry(renames.newest_path(&file.path)).or_default();
+                    // Commits are walked from newest to oldest
+                    if file_churn.commits == 0 {
+                        file_churn.category = file.category;
+                    }
+                    if let Some(renamed_from) = file.renamed_from {
+                        file_churn.previous_paths.insert(renamed_from);
+                    }
+                    let shares = self.shares(file.stats.clone(), credited.clone());
+                    file_churn.add_commit(&file.stats, shares);
+                }
+                Ok(churn)
+            },
+        )
+    }
+
+    /// Get the commits, insertions, and deletions of every contributor bucketed by [`Period`](enum.Period.html)
+    ///
+    /// Periods are keyed by the date they start on and commits are bucketed by [`Commit::date`](struct.Commit.html#method.date),
+    /// periods without any commits are left out
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{GitDetective, Period};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let gd = GitDetective::open(".")?;
+    /// for (start, contributors) in gd.timeline(Period::Day)? {
+    ///   let commits: usize = contributors.values().map(|activity| activity.commits).sum();
+    ///   println!("{}: {} commits", Period::Day.label(start), commits);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - Unable to walk commits
+    /// - Unable to get [`git2::Tree`](https://docs.rs/git2/latest/git2/struct.Tree.html) for a [`git2::Commit`](https://docs.rs/git2/latest/git2/struct.Commit.html)
+    /// - Unable to get the stats for a [`git2::Diff`](https://docs.rs/git2/latest/git2/struct.Diff.html)
+    pub fn timeline(
+        &self,
+        period: Period,
+    ) -> Result<BTreeMap<NaiveDate, HashMap<Contributor, Activity>>, Error> {
+        self.timeline_in(&HistoryQuery::default(), period)
+    }
+
+    /// Get the activity of every contributor bucketed by [`Period`](enum.Period.html) for the commits selected
+    /// by a [`HistoryQuery`](struct.HistoryQuery.html)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use git_detective::Error;
+    /// use git_detective::{GitDetective, HistoryQuery, Period};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let gd = GitDetective::open(".")?;
+    /// let mut query = HistoryQuery::new();
+    /// query.range("HEAD~1..HEAD");
+    ///
+    /// let timeline = gd.timeline_in(&query, Period::Month)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - Revision or range in `query` doesn't exist [`GitError`](enum.Error.html#variant.GitError)
+    /// - Unable to get [`git2::Tree`](https://docs.rs/git2/latest/git2/struct.Tree.html) for a [`git2::Commit`](https://docs.rs/git2/latest/git2/struct.Commit.html)
+    /// - Unable to get the stats for a [`git2::Diff`](https://docs.rs/git2/latest/git2/struct.Diff.html)
+    pub fn timeline_in(
+        &self,
+        query: &HistoryQuery,
+        period: Period,
+    ) -> Result<BTreeMap<NaiveDate, HashMap<Contributor, Activity>>, Error> {
+        let identities = self.identities()?;
+        let exclusions = self.exclusions()?;
+        let classifier = self.query_classifier(query)?;
+        query.walk(&self.repository)?.try_fold(
+            BTreeMap::new(),
+            |mut timeline: BTreeMap<_, HashMap<_, Activity>>, commit| -> Result<_, Error> {
+                let stats = self.commit_diff_stats(&commit, &exclusions, &classifier)?;
+                let credited = self.credited(&identities, &commit);
+                let start = period.start(Commit::from(commit).date());
+                let contribs = timeline.entry(start).or_default();
+                for (contributor, share) in self.shares(stats, credited) {
+                    *contributor::entry(contribs, contributor) += Activity {
+                        commits: 1,
+                        insertions: share.insertions,
+                        deletions: share.deletions,
+                    };
+                }
+                Ok(timeline)
+            },
+        )
     }
 }
@@ -0,0 +1,112 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
+use crate::Error;
+
+/// A single line of a `.mailmap` file
+#[derive(Debug, Clone)]
+struct MailmapEntry {
+    real_name: Option<String>,
+    real_email: Option<String>,
+    replace_name: Option<String>,
+    replace_email: String,
+}
+
+/// Canonicalizes identities using the rules of [`gitmailmap`](https://git-scm.com/docs/gitmailmap)
+///
+/// Unlike [`git2::Mailmap`](https://docs.rs/git2/latest/git2/struct.Mailmap.html) this can be shared
+/// between threads, which is required in [`final_contributions`](struct.GitDetective.html#method.final_contributions)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Mailmap {
+    entries: Vec<MailmapEntry>,
+}
+
+impl Mailmap {
+    /// Parse a mailmap buffer, adding its entries to the already existing ones
+    ///
+    /// Entries that appear later take precedence, the same as git
+    pub(crate) fn add_buffer<S: AsRef<str>>(&mut self, buffer: S) {
+        let entries = buffer.as_ref().lines().filter_map(Mailmap::parse_line);
+        self.entries.extend(entries);
+    }
+
+    /// Read and parse a mailmap file
+    ///
+    /// # Errors
+    /// - Failed to read file [`IOError`](enum.Error.html#variant.IOError)
+    pub(crate) fn add_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let buffer = read_to_string(path).map_err(|err| Error::IOError(err, path.into()))?;
+        self.add_buffer(buffer);
+        Ok(())
+    }
+
+    /// Parses `Proper Name <proper@email> Commit Name <commit@email>` where every part except
+    /// for one email is optional
+    fn parse_line(line: &str) -> Option<MailmapEntry> {
+        let line = match line.find('#') {
+            Some(index) => &line[..index],
+            None => line,
+        };
+        let (first_name, first_email, rest) = Mailmap::parse_identity(line)?;
+        match Mailmap::parse_identity(rest) {
+            Some((replace_name, replace_email, _)) => Some(MailmapEntry {
+                real_name: first_name,
+                real_email: Some(first_email),
+                replace_name,
+                replace_email,
+            }),
+            None => Some(MailmapEntry {
+                real_name: first_name,
+                real_email: None,
+                replace_name: None,
+                replace_email: first_email,
+            }),
+        }
+    }
+
+    /// Parses `Name <email>` returning the optional name, email, and the remaining input
+    fn parse_identity(input: &str) -> Option<(Option<String>, String, &str)> {
+        let start = input.find('<')?;
+        let end = start + input[start..].find('>')?;
+        let name = input[..start].trim();
+        let name = if name.is_empty() {
+            None
+        } else {
+            Some(name.to_string())
+        };
+        let email = input[start + 1..end].trim().to_string();
+        Some((name, email, &input[end + 1..]))
+    }
+
+    /// Resolve a name and email to their canonical forms
+    ///
+    /// Entries matching both name and email take priority over those matching only email,
+    /// all comparisons are case insensitive
+    pub(crate) fn resolve<'a>(&'a self, name: &'a str, email: &'a str) -> (&'a str, &'a str) {
+        let matches_email = |entry: &&MailmapEntry| entry.replace_email.eq_ignore_ascii_case(email);
+        let by_name = self
+            .entries
+            .iter()
+            .rev()
+            .filter(matches_email)
+            .find(|entry| match &entry.replace_name {
+                Some(replace_name) => replace_name.eq_ignore_ascii_case(name),
+                None => false,
+            });
+        let entry = by_name.or_else(|| {
+            self.entries
+                .iter()
+                .rev()
+                .filter(matches_email)
+                .find(|entry| entry.replace_name.is_none())
+        });
+        match entry {
+            Some(entry) => (
+                entry.real_name.as_deref().unwrap_or(name),
+                entry.real_email.as_deref().unwrap_or(email),
+            ),
+            None => (name, email),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+/// How changes in merge commits are credited
+///
+/// Applies to [`diff_stats`](struct.GitDetective.html#method.diff_stats),
+/// [`files_contributed_to`](struct.GitDetective.html#method.files_contributed_to), and
+/// [`file_churn`](struct.GitDetective.html#method.file_churn), merges are always counted in
+/// [`DiffStats::merges`](struct.DiffStats.html#structfield.merges)
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::{GitDetective, MergePolicy};
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut gd = GitDetective::open(".")?;
+/// gd.set_merge_policy(MergePolicy::ConflictResolution);
+///
+/// for (author, diff_stat) in gd.diff_stats()? {
+///   println!("{} merged {} times", author, diff_stat.merges);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum MergePolicy {
+    /// Merges aren't credited with any changes, the same as `git log -p`
+    #[default]
+    Skip,
+    /// Merges are diffed against their first parent, crediting the merger with every change
+    /// brought in from the other branches
+    FirstParent,
+    /// Only changes that differ from every parent are credited, such as resolved conflicts,
+    /// the same as `git show --cc`
+    ConflictResolution,
+}
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+
+/// Length of the periods a [`timeline`](struct.GitDetective.html#method.timeline) is bucketed into
+///
+/// Periods are in UTC and identified by the date they start on
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::{GitDetective, Period};
+///
+/// # fn main() -> Result<(), Error> {
+/// let gd = GitDetective::open(".")?;
+/// for (start, activity) in gd.timeline(Period::Week)? {
+///   println!("{}: {} contributors", Period::Week.label(start), activity.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Period {
+    /// Calendar days
+    Day,
+    /// ISO weeks, starting on Monday
+    #[default]
+    Week,
+    /// Calendar months
+    Month,
+}
+
+impl Period {
+    /// First day of the period that contains `date`
+    pub fn start(self, date: DateTime<Utc>) -> NaiveDate {
+        let day = date.naive_utc().date();
+        match self {
+            Period::Day => day,
+            Period::Week => {
+                let week = day.iso_week();
+                NaiveDate::from_isoywd_opt(week.year(), week.week(), Weekday::Mon).unwrap_or(day)
+            }
+            Period::Month => day.with_day(1).unwrap_or(day),
+        }
+    }
+
+    /// Human readable name of the period starting on `start`, such as `2020-05-11`, `2020-W20`, or `2020-05`
+    pub fn label(self, start: NaiveDate) -> String {
+        let format = match self {
+            Period::Day => "%Y-%m-%d",
+            Period::Week => "%G-W%V",
+            Period::Month => "%Y-%m",
+        };
+        start.format(format).to_string()
+    }
+}
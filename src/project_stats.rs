@@ -1,12 +1,19 @@
-use std::collections::{hash_map::Entry, hash_map::Iter, HashMap};
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
+use std::collections::{hash_map::Iter, HashMap};
 use std::ops::AddAssign;
 
+use crate::contributor::{self, Contributor};
 use crate::stats::Stats;
+use crate::{FileCategory, WhitespaceMode};
 
 /// Project Stats
 ///
 /// Contains contributions for all contributors separated on language
 ///
+/// Files that aren't source code are only counted in their own [`category`](struct.ProjectStats.html#method.category)
+/// when [`CategoryPolicy::Separate`](enum.CategoryPolicy.html#variant.Separate) is used
+///
 /// # Example
 ///
 /// ```
@@ -14,7 +21,7 @@ use crate::stats::Stats;
 /// use git_detective::GitDetective;
 ///
 /// # fn main() -> Result<(), Error> {
-/// let mut gd = GitDetective::open(".")?;
+/// let gd = GitDetective::open(".")?;
 /// let project_stats = gd.final_contributions()?;
 ///
 /// for contributor in project_stats.contributors() {
@@ -25,9 +32,11 @@ use crate::stats::Stats;
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct ProjectStats {
-    stats: HashMap<String, HashMap<&'static str, Stats>>,
+    stats: HashMap<Contributor, HashMap<&'static str, Stats>>,
+    whitespace_mode: WhitespaceMode,
+    categories: HashMap<FileCategory, ProjectStats>,
 }
 
 impl ProjectStats {
@@ -35,6 +44,41 @@ impl ProjectStats {
     pub fn new() -> Self {
         Self {
             stats: HashMap::new(),
+            whitespace_mode: WhitespaceMode::default(),
+            categories: HashMap::new(),
+        }
+    }
+
+    /// Contributions to files of a [`FileCategory`](enum.FileCategory.html) that were counted separately
+    pub fn category(&self, category: FileCategory) -> Option<&ProjectStats> {
+        self.categories.get(&category)
+    }
+
+    /// Iterate over all separately counted [`FileCategory`](enum.FileCategory.html)s
+    pub fn categories(&self) -> impl Iterator<Item = (&FileCategory, &ProjectStats)> {
+        self.categories.iter()
+    }
+
+    /// Move all contributions into the bucket for `category`, source code is left as is
+    pub(crate) fn into_category(self, category: FileCategory) -> Self {
+        if category == FileCategory::Source {
+            return self;
+        }
+        let mut project_stats = Self::new();
+        project_stats.categories.insert(category, self);
+        project_stats
+    }
+
+    /// Whether lines were credited to whoever last changed their whitespace
+    pub fn whitespace_mode(&self) -> WhitespaceMode {
+        self.whitespace_mode
+    }
+
+    /// Record the [`WhitespaceMode`](enum.WhitespaceMode.html) lines were credited with
+    pub(crate) fn set_whitespace_mode(&mut self, mode: WhitespaceMode) {
+        self.whitespace_mode = mode;
+        for project_stats in self.categories.values_mut() {
+            project_stats.set_whitespace_mode(mode);
         }
     }
 
@@ -48,9 +92,9 @@ impl ProjectStats {
         )
     }
 
-    /// Check whether [`ProjectStats`](struct.ProjectStats.html) is empty
+    /// Check whether [`ProjectStats`](struct.ProjectStats.html) is empty, including every separately counted category
     pub fn is_empty(&self) -> bool {
-        self.stats.is_empty()
+        self.stats.is_empty() && self.categories.values().all(ProjectStats::is_empty)
     }
 
     /// Get contributions by name broken down by language
@@ -73,51 +117,66 @@ impl ProjectStats {
     }
 
     /// Get an Iterator over all Contributors
-    pub fn contributors(&self) -> impl Iterator<Item = &String> {
+    pub fn contributors(&self) -> impl Iterator<Item = &Contributor> {
         self.stats.keys()
     }
 
     /// Inserts into the nested HashMap<HashMap> structure
-    pub(crate) fn insert<S: Into<String>>(&mut self, name: S, lang: &'static str, stats: Stats) {
-        match self.stats.entry(name.into()) {
-            Entry::Occupied(mut occupied) => {
-                let stats_map = occupied.get_mut();
-                let lang_stats = stats_map.entry(lang).or_insert_with(Stats::default);
-                *lang_stats += stats;
-            }
-            Entry::Vacant(vacant) => {
-                let mut lang_stat = HashMap::new();
-                lang_stat.insert(lang, stats);
-                vacant.insert(lang_stat);
-            }
-        }
+    pub(crate) fn insert(&mut self, contributor: Contributor, lang: &'static str, stats: Stats) {
+        let stats_map = contributor::entry(&mut self.stats, contributor);
+        let lang_stats = stats_map.entry(lang).or_default();
+        *lang_stats += stats;
     }
 
     /// Iterate over [`ProjectStats`](struct.ProjectStats.html)
-    pub fn iter(&self) -> Iter<'_, String, HashMap<&str, Stats>> {
+    pub fn iter(&self) -> Iter<'_, Contributor, HashMap<&str, Stats>> {
         self.stats.iter()
     }
 }
 
 #[doc(hidden)]
-impl From<(&'static str, HashMap<String, Stats>)> for ProjectStats {
-    fn from(lang_stats: (&'static str, HashMap<String, Stats>)) -> Self {
+impl From<(&'static str, HashMap<Contributor, Stats>)> for ProjectStats {
+    fn from(lang_stats: (&'static str, HashMap<Contributor, Stats>)) -> Self {
         let (lang, file_stats) = lang_stats;
         let mut project_stats = Self::new();
-        for (author, stats) in file_stats {
-            project_stats.insert(author, lang, stats);
+        for (contributor, stats) in file_stats {
+            project_stats.insert(contributor, lang, stats);
         }
         project_stats
     }
 }
 
+/// Serialized with every contributor's stats by language and the separately counted categories, sorted by name
+#[cfg(feature = "serde")]
+impl serde::Serialize for ProjectStats {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let contributors: BTreeMap<_, BTreeMap<_, _>> = self
+            .stats
+            .iter()
+            .map(|(contributor, lang_stats)| (contributor, lang_stats.iter().collect()))
+            .collect();
+        let categories: BTreeMap<_, _> = self.categories.iter().collect();
+        let mut state = serializer.serialize_struct("ProjectStats", 3)?;
+        state.serialize_field("contributors", &contributors)?;
+        state.serialize_field("whitespace_mode", &self.whitespace_mode)?;
+        state.serialize_field("categories", &categories)?;
+        state.end()
+    }
+}
+
 #[doc(hidden)]
 impl AddAssign for ProjectStats {
     fn add_assign(&mut self, other: Self) {
-        for (lang, contrib_stats) in other.stats {
-            for (author, stats) in contrib_stats {
-                self.insert(&*lang, author, stats);
+        for (contributor, lang_stats) in other.stats {
+            for (lang, stats) in lang_stats {
+                self.insert(contributor.clone(), lang, stats);
             }
         }
+        for (category, project_stats) in other.categories {
+            *self.categories.entry(category).or_default() += project_stats;
+        }
+        self.whitespace_mode = other.whitespace_mode;
     }
 }
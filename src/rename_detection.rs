@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use git2::DiffFindOptions;
+
+use crate::Error;
+
+/// Default similarity in percent for a file to be a rename or copy, the same as git
+const DEFAULT_THRESHOLD: u16 = 50;
+
+/// How renamed and copied files are detected in commit diffs
+///
+/// Renamed and copied files are only credited with the lines that changed, instead of deleting
+/// and adding the whole file. Renamed files are followed through history, so their churn and
+/// contributors are reported under their newest path.
+///
+/// Renames and copies are detected by default
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::{GitDetective, RenameDetection};
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut gd = GitDetective::open(".")?;
+/// let mut detection = RenameDetection::new();
+/// detection.copies(false).threshold(75);
+/// gd.set_rename_detection(detection);
+///
+/// let diff_stats = gd.diff_stats()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RenameDetection {
+    renames: bool,
+    copies: bool,
+    threshold: u16,
+}
+
+impl Default for RenameDetection {
+    fn default() -> Self {
+        Self {
+            renames: true,
+            copies: true,
+            threshold: DEFAULT_THRESHOLD,
+        }
+    }
+}
+
+impl RenameDetection {
+    /// Construct a new RenameDetection that detects renames and copies at 50% similarity
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Detect renamed files, the same as `git log -M`
+    pub fn renames(&mut self, renames: bool) -> &mut Self {
+        self.renames = renames;
+        self
+    }
+
+    /// Detect files copied from a file modified in the same commit, the same as `git log -C`
+    pub fn copies(&mut self, copies: bool) -> &mut Self {
+        self.copies = copies;
+        self
+    }
+
+    /// Similarity in percent from 0 to 100 for a file to be a rename or copy
+    ///
+    /// Defaults to 50, the same as git
+    pub fn threshold(&mut self, threshold: u16) -> &mut Self {
+        self.threshold = threshold.min(100);
+        self
+    }
+
+    /// Mark renamed and copied files in `diff`
+    ///
+    /// # Errors
+    /// - Failed to compare files [`GitError`](enum.Error.html#variant.GitError)
+    pub(crate) fn find_similar(
+        &self,
+        diff: &mut git2::Diff<'_>,
+        ignore_whitespace: bool,
+    ) -> Result<(), Error> {
+        if !self.renames && !self.copies {
+            return Ok(());
+        }
+        let mut options = DiffFindOptions::new();
+        options
+            .renames(self.renames)
+            .copies(self.copies)
+            .rename_threshold(self.threshold)
+            .copy_threshold(self.threshold)
+            .ignore_whitespace(ignore_whitespace);
+        diff.find_similar(Some(&mut options))?;
+        Ok(())
+    }
+}
+
+/// Newest path of files renamed in commits that were already walked
+///
+/// Commits must be walked from newest to oldest, the same as `git log --follow`
+#[derive(Debug, Default)]
+pub(crate) struct RenameHistory {
+    newest: HashMap<PathBuf, PathBuf>,
+}
+
+impl RenameHistory {
+    /// Path that `path` was renamed to in the newest walked commit
+    pub(crate) fn newest_path(&self, path: &Path) -> PathBuf {
+        self.newest
+            .get(path)
+            .cloned()
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+
+    /// Record that `old` was renamed to `new`, older commits changing `old` changed the newest path of `new`
+    pub(crate) fn renamed(&mut self, old: &Path, new: &Path) {
+        let newest = self.newest_path(new);
+        self.newest.insert(old.to_path_buf(), newest);
+    }
+}
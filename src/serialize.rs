@@ -0,0 +1,70 @@
+//! Serialization of types that don't implement `Serialize` themselves, only built with the `serde` feature
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use git2::{Oid, Status};
+use serde::{Serialize, Serializer};
+
+/// Names of every [`Status`](https://docs.rs/git2/latest/git2/struct.Status.html) flag, in the same order as git2
+const STATUSES: [(Status, &str); 12] = [
+    (Status::INDEX_NEW, "index_new"),
+    (Status::INDEX_MODIFIED, "index_modified"),
+    (Status::INDEX_DELETED, "index_deleted"),
+    (Status::INDEX_RENAMED, "index_renamed"),
+    (Status::INDEX_TYPECHANGE, "index_typechange"),
+    (Status::WT_NEW, "wt_new"),
+    (Status::WT_MODIFIED, "wt_modified"),
+    (Status::WT_DELETED, "wt_deleted"),
+    (Status::WT_TYPECHANGE, "wt_typechange"),
+    (Status::WT_RENAMED, "wt_renamed"),
+    (Status::IGNORED, "ignored"),
+    (Status::CONFLICTED, "conflicted"),
+];
+
+/// Dates as RFC 3339 strings such as `2020-05-14T23:30:00+00:00`
+pub(crate) fn date<S: Serializer>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&date.to_rfc3339())
+}
+
+/// Object ids as full hex strings
+pub(crate) fn oid<S: Serializer>(oid: &Oid, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&oid.to_string())
+}
+
+/// Statuses as a list of their flags, unmodified files are an empty list
+pub(crate) fn status<S: Serializer>(status: &Status, serializer: S) -> Result<S::Ok, S::Error> {
+    STATUSES
+        .iter()
+        .filter(|(flag, _)| status.contains(*flag))
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .serialize(serializer)
+}
+
+/// Maps sorted by key, so output doesn't change between runs
+pub(crate) fn sorted<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: Ord + Serialize,
+    V: Serialize,
+    S: Serializer,
+{
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+/// Maps of maps sorted by both keys
+pub(crate) fn sorted_nested<K, K2, V, S>(
+    map: &HashMap<K, HashMap<K2, V>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    K: Ord + Serialize,
+    K2: Ord + Serialize,
+    V: Serialize,
+    S: Serializer,
+{
+    map.iter()
+        .map(|(key, inner)| (key, inner.iter().collect::<BTreeMap<_, _>>()))
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
+}
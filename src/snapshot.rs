@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use git2::Oid;
+
+use crate::{Contributor, FileCategory, ProjectStats, StatsDelta};
+
+/// Final contributions at a single revision, such as a tag for each release
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::GitDetective;
+///
+/// # fn main() -> Result<(), Error> {
+/// let gd = GitDetective::open(".")?;
+/// for snapshot in gd.snapshots()? {
+///   println!(
+///     "{} ({}): {} lines",
+///     snapshot.revision,
+///     snapshot.date,
+///     snapshot.stats.total_lines()
+///   );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Snapshot {
+    /// Revision the snapshot was taken at, such as the name of a tag
+    pub revision: String,
+    /// Commit the revision points to
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::serialize::oid"))]
+    pub id: Oid,
+    /// Date the commit was commited
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::serialize::date"))]
+    pub date: DateTime<Utc>,
+    /// Final contributions at the commit, the same as [`final_contributions_at`](struct.GitDetective.html#method.final_contributions_at)
+    pub stats: ProjectStats,
+    /// Change in every contributor's total lines since the previous snapshot, the first snapshot is compared
+    /// to an empty repository
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::serialize::sorted"))]
+    pub delta: HashMap<Contributor, StatsDelta>,
+    /// Change in every contributor's lines of each separately counted [`category`](struct.ProjectStats.html#method.category)
+    /// since the previous snapshot
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::serialize::sorted_nested")
+    )]
+    pub categories: HashMap<FileCategory, HashMap<Contributor, StatsDelta>>,
+}
+
+/// Change in every contributor's total lines from `previous` to `current`
+pub(crate) fn delta(
+    previous: Option<&ProjectStats>,
+    current: &ProjectStats,
+) -> HashMap<Contributor, StatsDelta> {
+    let empty = ProjectStats::new();
+    let previous = previous.unwrap_or(&empty);
+    previous
+        .contributors()
+        .chain(current.contributors())
+        .map(|contributor| {
+            let before = previous
+                .total_contribs_by_name(contributor.name())
+                .unwrap_or_default();
+            let after = current
+                .total_contribs_by_name(contributor.name())
+                .unwrap_or_default();
+            (contributor.clone(), StatsDelta::new(before, after))
+        })
+        .collect()
+}
+
+/// Change in every contributor's lines from `previous` to `current` for each category in either of them
+pub(crate) fn category_delta(
+    previous: Option<&ProjectStats>,
+    current: &ProjectStats,
+) -> HashMap<FileCategory, HashMap<Contributor, StatsDelta>> {
+    let empty = ProjectStats::new();
+    let previous = previous.unwrap_or(&empty);
+    previous
+        .categories()
+        .chain(current.categories())
+        .map(|(category, _)| {
+            let after = current.category(*category).unwrap_or(&empty);
+            (*category, delta(previous.category(*category), after))
+        })
+        .collect()
+}
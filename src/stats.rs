@@ -23,6 +23,7 @@ use tokei::LineType;
 /// # }
 /// ```
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Stats {
     /// The number of total lines
     pub lines: usize,
@@ -0,0 +1,57 @@
+use std::ops::AddAssign;
+
+use crate::Stats;
+
+/// Change in [`Stats`](struct.Stats.html) between two snapshots, lines that were removed or
+/// rewritten by someone else are negative
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::GitDetective;
+///
+/// # fn main() -> Result<(), Error> {
+/// let gd = GitDetective::open(".")?;
+/// for snapshot in gd.snapshots_at(["HEAD~1", "HEAD"])? {
+///   for (contributor, delta) in snapshot.delta {
+///     println!("{}: {:+} lines", contributor, delta.lines);
+///   }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StatsDelta {
+    /// Change in total lines
+    pub lines: isize,
+    /// Change in blank lines
+    pub blanks: isize,
+    /// Change in comment lines
+    pub comments: isize,
+    /// Change in lines of code
+    pub code: isize,
+}
+
+impl StatsDelta {
+    /// Change from `before` to `after`
+    pub(crate) fn new(before: Stats, after: Stats) -> Self {
+        let change = |before: usize, after: usize| after as isize - before as isize;
+        Self {
+            lines: change(before.lines, after.lines),
+            blanks: change(before.blanks, after.blanks),
+            comments: change(before.comments, after.comments),
+            code: change(before.code, after.code),
+        }
+    }
+}
+
+impl AddAssign for StatsDelta {
+    fn add_assign(&mut self, other: Self) {
+        self.lines += other.lines;
+        self.blanks += other.blanks;
+        self.comments += other.comments;
+        self.code += other.code;
+    }
+}
@@ -0,0 +1,37 @@
+/// Whether whitespace-only changes are credited
+///
+/// Applies to [`diff_stats`](struct.GitDetective.html#method.diff_stats) and
+/// [`final_contributions`](struct.GitDetective.html#method.final_contributions), both report
+/// the mode they were counted with so results can be labeled
+///
+/// # Example
+///
+/// ```
+/// # use git_detective::Error;
+/// use git_detective::{GitDetective, WhitespaceMode};
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut gd = GitDetective::open(".")?;
+/// gd.set_whitespace_mode(WhitespaceMode::Insensitive);
+///
+/// let project_stats = gd.final_contributions()?;
+/// assert_eq!(project_stats.whitespace_mode(), WhitespaceMode::Insensitive);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum WhitespaceMode {
+    /// Every change is credited, including reindenting lines
+    #[default]
+    Sensitive,
+    /// Changes that only add, remove, or replace whitespace are ignored, the same as `git diff -w`
+    /// and `git blame -w`
+    ///
+    /// Reindented lines stay credited to whoever wrote them
+    Insensitive,
+}
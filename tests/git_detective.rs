@@ -1,12 +1,53 @@
 #[cfg(test)]
 mod git_detective_integration_tests {
-    use chrono::offset::Utc;
+    use chrono::offset::{TimeZone, Utc};
+    use chrono::NaiveDate;
+    use std::collections::HashMap;
     use std::fs::remove_dir_all;
     use std::mem::discriminant;
     use std::path::PathBuf;
 
     use git_detective::Error;
-    use git_detective::{GitDetective, RepositoryState};
+    use git_detective::{
+        BlameSettings, CategoryPolicy, Contributor, CreditPolicy, FileCategory, GitDetective,
+        HistoryQuery, IdentityMatcher, IdentityResolver, MergePolicy, Period, RenameDetection,
+        RepositoryState, Stats, WhitespaceMode,
+    };
+
+    /// Commit every change in the working directory of `repository` as `author`, merging `merged`
+    fn commit_all(
+        repository: &git2::Repository,
+        author: &str,
+        merged: Option<git2::Oid>,
+    ) -> Result<git2::Oid, Error> {
+        let signature = git2::Signature::now(author, &format!("{}@example.com", author))?;
+        commit_all_as(repository, &signature, merged)
+    }
+
+    /// Commit every change in the working directory of `repository` with `signature`, merging `merged`
+    fn commit_all_as(
+        repository: &git2::Repository,
+        signature: &git2::Signature<'_>,
+        merged: Option<git2::Oid>,
+    ) -> Result<git2::Oid, Error> {
+        let mut index = repository.index()?;
+        index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
+        index.update_all(["*"].iter(), None)?;
+        index.write()?;
+        let tree = repository.find_tree(index.write_tree()?)?;
+        let mut parents: Vec<_> = repository
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .into_iter()
+            .collect();
+        if let Some(merged) = merged {
+            parents.push(repository.find_commit(merged)?);
+        }
+        let parents: Vec<_> = parents.iter().collect();
+        let message = signature.name().unwrap_or_default();
+        let id = repository.commit(Some("HEAD"), signature, signature, message, &tree, &parents)?;
+        Ok(id)
+    }
 
     #[test]
     fn clone() -> Result<(), Error> {
@@ -121,6 +162,532 @@ mod git_detective_integration_tests {
         Ok(())
     }
 
+    #[test]
+    fn exclude_patterns() -> Result<(), Error> {
+        let mut gd = GitDetective::open(".")?;
+        let before: usize = gd
+            .diff_stats()?
+            .values()
+            .map(|stats| stats.insertions)
+            .sum();
+        let lines_before = gd.final_contributions()?.total_lines();
+        gd.exclude("src/**")?;
+        gd.exclude("*.md")?;
+        gd.include("README.md")?;
+        let list = gd.ls()?;
+        assert!(list.iter().all(|stat| !stat.path.starts_with("src/")));
+        assert!(list.iter().any(|stat| &stat.path == "README.md"));
+        let after: usize = gd
+            .diff_stats()?
+            .values()
+            .map(|stats| stats.insertions)
+            .sum();
+        assert!(after < before);
+        let contrib_files = gd.files_contributed_to()?;
+        assert!(contrib_files
+            .values()
+            .flatten()
+            .all(|path| !path.starts_with("src")));
+        assert!(gd.final_contributions()?.total_lines() < lines_before);
+        assert!(gd.exclude("src/{lib,main.rs").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn category_policy() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_category_policy");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        let attributes = "*.pb.rs linguist-generated\n\
+                          keep.pb.rs -linguist-generated\n\
+                          external/** linguist-vendored\n\
+                          *.dat -diff\n";
+        let files = [
+            (".gitattributes", attributes),
+            ("docs/.gitattributes", "*.md linguist-documentation\n"),
+            ("src/main.rs", "fn main() {}\n"),
+            ("api.pb.rs", "fn api() {}\n"),
+            ("keep.pb.rs", "fn keep() {}\n"),
+            ("external/lib.rs", "fn lib() {}\n"),
+            ("data.dat", "1 2 3\n"),
+            ("docs/guide.md", "# Guide\n"),
+            // Patterns in a nested `.gitattributes` only match below its directory
+            ("README.md", "# Readme\n"),
+        ];
+        for (file, content) in files.iter() {
+            let file_path = path.join(file);
+            std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+            std::fs::write(&file_path, content).unwrap();
+        }
+        commit_all(&repository, "Alice", None)?;
+
+        // Both `.gitattributes`, main.rs, keep.pb.rs, and README.md are source code
+        let source_lines = 4 + 1 + 1 + 1 + 1;
+        let mut gd = GitDetective::open(&path)?;
+        let excluded = gd.final_contributions()?;
+        assert_eq!(excluded.categories().count(), 0);
+        assert_eq!(excluded.total_lines(), source_lines);
+        let by_file = gd.final_contributions_by_file()?;
+        assert_eq!(by_file.len(), 5);
+        assert!(!by_file.contains_key(&PathBuf::from("api.pb.rs")));
+        gd.set_category_policy(CategoryPolicy::Separate);
+        let separate = gd.final_contributions()?;
+        assert_eq!(separate.total_lines(), source_lines);
+        let category_lines = |category| {
+            separate
+                .category(category)
+                .map_or(0, |stats| stats.total_lines())
+        };
+        assert_eq!(category_lines(FileCategory::Generated), 1);
+        assert_eq!(category_lines(FileCategory::Vendored), 1);
+        assert_eq!(category_lines(FileCategory::Binary), 1);
+        assert_eq!(category_lines(FileCategory::Documentation), 1);
+        let by_file = gd.final_contributions_by_file()?;
+        assert_eq!(by_file.len(), files.len());
+        let api = &by_file[&PathBuf::from("api.pb.rs")];
+        assert_eq!(api.total_lines(), 0);
+        assert!(!api.is_empty());
+        assert_eq!(
+            api.category(FileCategory::Generated).unwrap().total_lines(),
+            1
+        );
+        gd.set_category_policy(CategoryPolicy::Include);
+        let included = gd.final_contributions()?;
+        assert_eq!(included.categories().count(), 0);
+        assert_eq!(included.total_lines(), source_lines + 4);
+
+        // Ranges are categorized by the `.gitattributes` of their newest revision, not `HEAD`
+        std::fs::write(path.join("api.pb.rs"), "fn api() {}\nfn more() {}\n").unwrap();
+        commit_all(&repository, "Bob", None)?;
+        std::fs::write(
+            path.join(".gitattributes"),
+            "external/** linguist-vendored\n",
+        )
+        .unwrap();
+        commit_all(&repository, "Carol", None)?;
+        gd.set_category_policy(CategoryPolicy::Separate);
+        let mut generated = HistoryQuery::new();
+        generated.range("HEAD~2..HEAD~1");
+        let bob = &gd.diff_stats_in(&generated)?["Bob"];
+        assert_eq!(bob.insertions, 0);
+        assert_eq!(bob.categories[&FileCategory::Generated].insertions, 1);
+        let bob = &gd.diff_stats()?["Bob"];
+        assert_eq!(bob.insertions, 1);
+        assert!(bob.categories.is_empty());
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn heuristic_categories() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_heuristic_categories");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        let files = [
+            ("src/main.rs", "fn main() {}\n".to_string()),
+            ("Cargo.lock", "version = 3\n".to_string()),
+            (
+                "node_modules/left-pad/index.js",
+                "module.exports = 1;\n".to_string(),
+            ),
+            (
+                "pb.rs",
+                "// Code generated by protoc. DO NOT EDIT.\nfn pb() {}\n".to_string(),
+            ),
+            ("bundle.js", format!("var a={};\n", "1+".repeat(600) + "1")),
+            // Doc comments and prose mentioning generated code aren't headers
+            (
+                "notes.rs",
+                "/// Values generated by the parser\n// Do not edit without asking\nfn notes() {}\n"
+                    .to_string(),
+            ),
+        ];
+        let mut index = repository.index()?;
+        for (file, content) in files.iter() {
+            let file_path = path.join(file);
+            std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+            std::fs::write(&file_path, content).unwrap();
+            index.add_path(std::path::Path::new(file))?;
+        }
+        let tree = repository.find_tree(index.write_tree()?)?;
+        let signature = git2::Signature::now("Alice", "alice@example.com")?;
+        repository.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])?;
+        index.write()?;
+
+        let mut gd = GitDetective::open(&path)?;
+        // Heuristics are opt-in, every file without attributes is source code by default
+        assert_eq!(gd.final_contributions()?.total_lines(), 9);
+        gd.set_heuristics(true);
+        assert_eq!(gd.final_contributions()?.total_lines(), 4);
+        gd.set_category_policy(CategoryPolicy::Separate);
+        let project_stats = gd.final_contributions()?;
+        assert_eq!(project_stats.total_lines(), 4);
+        let category_lines = |category| {
+            project_stats
+                .category(category)
+                .map_or(0, |stats| stats.total_lines())
+        };
+        assert_eq!(category_lines(FileCategory::Generated), 3);
+        assert_eq!(category_lines(FileCategory::Vendored), 1);
+        assert_eq!(category_lines(FileCategory::Minified), 1);
+        let diff_stats = gd.diff_stats()?;
+        let alice = diff_stats.values().next().unwrap();
+        assert_eq!(alice.insertions, 4);
+        assert_eq!(alice.categories[&FileCategory::Generated].insertions, 3);
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn diff_stats_by_language() -> Result<(), Error> {
+        let mut gd = GitDetective::open(".")?;
+        gd.set_credit_policy(CreditPolicy::SplitEvenly);
+        let diff_stats = gd.diff_stats()?;
+        assert!(diff_stats
+            .values()
+            .any(|stats| stats.languages.contains_key("Rust")));
+        for stats in diff_stats.values() {
+            let insertions: usize = stats.languages.values().map(|lang| lang.insertions).sum();
+            let deletions: usize = stats.languages.values().map(|lang| lang.deletions).sum();
+            assert_eq!(insertions, stats.insertions);
+            assert_eq!(deletions, stats.deletions);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn diff_stats_line_types() -> Result<(), Error> {
+        let mut gd = GitDetective::open(".")?;
+        gd.set_credit_policy(CreditPolicy::SplitEvenly);
+        let diff_stats = gd.diff_stats()?;
+        assert!(diff_stats.values().any(|stats| stats.inserted.comments > 0));
+        for stats in diff_stats.values() {
+            assert_eq!(stats.inserted.lines, stats.insertions);
+            assert_eq!(stats.deleted.lines, stats.deletions);
+            for lines in [stats.inserted, stats.deleted].iter() {
+                assert_eq!(lines.code + lines.comments + lines.blanks, lines.lines);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn file_churn() -> Result<(), Error> {
+        let mut gd = GitDetective::open(".")?;
+        gd.set_credit_policy(CreditPolicy::SplitEvenly);
+        let churn = gd.file_churn()?;
+        let lib = &churn[&PathBuf::from("src/lib.rs")];
+        assert!(lib.commits > 1);
+        assert!(lib.authors() >= 1);
+        for file_churn in churn.values() {
+            let insertions: usize = file_churn
+                .contributors
+                .values()
+                .map(|stats| stats.insertions)
+                .sum();
+            let deletions: usize = file_churn
+                .contributors
+                .values()
+                .map(|stats| stats.deletions)
+                .sum();
+            assert_eq!(insertions, file_churn.insertions);
+            assert_eq!(deletions, file_churn.deletions);
+        }
+        let total: usize = churn.values().map(|file_churn| file_churn.insertions).sum();
+        let diff_total: usize = gd
+            .diff_stats()?
+            .values()
+            .map(|stats| stats.insertions)
+            .sum();
+        assert_eq!(total, diff_total);
+        Ok(())
+    }
+
+    #[test]
+    fn rename_detection() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_rename_detection");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        let lines: String = (0..20).map(|line| format!("let x = {};\n", line)).collect();
+        std::fs::write(path.join("old.rs"), &lines).unwrap();
+        commit_all(&repository, "Alice", None)?;
+        std::fs::remove_file(path.join("old.rs")).unwrap();
+        std::fs::write(path.join("new.rs"), lines + "let y = 0;\n").unwrap();
+        commit_all(&repository, "Bob", None)?;
+
+        let mut gd = GitDetective::open(&path)?;
+        let diff_stats = gd.diff_stats()?;
+        assert_eq!(diff_stats["Bob"].insertions, 1);
+        assert_eq!(diff_stats["Bob"].deletions, 0);
+        assert_eq!(diff_stats["Bob"].renames, 1);
+        let churn = gd.file_churn()?;
+        assert_eq!(churn.len(), 1);
+        let new = &churn[&PathBuf::from("new.rs")];
+        assert_eq!(new.commits, 2);
+        assert_eq!(new.authors(), 2);
+        assert!(new.previous_paths.contains(&PathBuf::from("old.rs")));
+        let contrib_files = gd.files_contributed_to()?;
+        assert!(contrib_files["Alice"].contains(&PathBuf::from("new.rs")));
+
+        let mut detection = RenameDetection::new();
+        detection.renames(false).copies(false);
+        gd.set_rename_detection(detection);
+        let diff_stats = gd.diff_stats()?;
+        assert_eq!(diff_stats["Bob"].insertions, 21);
+        assert_eq!(diff_stats["Bob"].deletions, 20);
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn merge_policy() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_merge_policy");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        let lines = |second: &str| format!("let a = 1;\n{}\nlet c = 3;\n", second);
+        std::fs::write(path.join("lib.rs"), lines("let b = 2;")).unwrap();
+        let base = commit_all(&repository, "Alice", None)?;
+        std::fs::write(path.join("lib.rs"), lines("let b = 20;")).unwrap();
+        std::fs::write(path.join("feature.rs"), "let f = 0;\n").unwrap();
+        let feature = commit_all(&repository, "Bob", None)?;
+        let base = repository.find_object(base, None)?;
+        repository.reset(&base, git2::ResetType::Hard, None)?;
+        std::fs::write(path.join("lib.rs"), lines("let b = 200;")).unwrap();
+        commit_all(&repository, "Alice", None)?;
+        std::fs::write(path.join("lib.rs"), lines("let b = 2000;\n// Resolved")).unwrap();
+        std::fs::write(path.join("feature.rs"), "let f = 0;\n").unwrap();
+        commit_all(&repository, "Carol", Some(feature))?;
+
+        let mut gd = GitDetective::open(&path)?;
+        let carol =
+            |gd: &GitDetective| -> Result<_, Error> { Ok(gd.diff_stats()?["Carol"].clone()) };
+        let skipped = carol(&gd)?;
+        assert_eq!(skipped.merges, 1);
+        assert_eq!(skipped.insertions, 0);
+        assert!(!gd.files_contributed_to()?.contains_key("Carol"));
+        gd.set_merge_policy(MergePolicy::FirstParent);
+        let first_parent = carol(&gd)?;
+        assert_eq!(first_parent.merges, 1);
+        assert_eq!(first_parent.insertions, 3);
+        assert_eq!(first_parent.deletions, 1);
+        gd.set_merge_policy(MergePolicy::ConflictResolution);
+        let resolution = carol(&gd)?;
+        assert_eq!(resolution.insertions, 2);
+        assert_eq!(resolution.deletions, 0);
+        assert_eq!(resolution.inserted.comments, 1);
+        let contrib_files = gd.files_contributed_to()?;
+        assert_eq!(contrib_files["Carol"].len(), 1);
+        assert!(contrib_files["Carol"].contains(&PathBuf::from("lib.rs")));
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn binary_files() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_binary_files");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        let image: Vec<u8> = (0..=255).cycle().take(1024).collect();
+        std::fs::write(path.join("main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(path.join("logo.png"), &image).unwrap();
+        commit_all(&repository, "Alice", None)?;
+        std::fs::write(path.join("logo.png"), &image[..512]).unwrap();
+        commit_all(&repository, "Alice", None)?;
+
+        let gd = GitDetective::open(&path)?;
+        let alice = &gd.diff_stats()?["Alice"];
+        assert_eq!(alice.insertions, 1);
+        assert_eq!(alice.binary.added, 1);
+        assert_eq!(alice.binary.modified, 1);
+        assert_eq!(alice.binary.bytes, 1024 + 512);
+        assert_eq!(gd.final_contributions()?.total_lines(), 1);
+        assert_eq!(gd.final_contributions_at("HEAD")?.total_lines(), 1);
+        let (_, logo) = gd.final_contributions_file("logo.png")?;
+        assert!(logo.is_empty());
+
+        // Marked binary in `.gitattributes`, so it's in the `Binary` category that's excluded by default
+        std::fs::write(path.join(".gitattributes"), "*.bin binary\n").unwrap();
+        std::fs::write(path.join("firmware.bin"), "text, but marked binary\n").unwrap();
+        commit_all(&repository, "Bob", None)?;
+        let mut gd = GitDetective::open(&path)?;
+        for policy in [CategoryPolicy::Exclude, CategoryPolicy::Separate].iter() {
+            gd.set_category_policy(*policy);
+            let bob = &gd.diff_stats()?["Bob"];
+            assert_eq!(bob.insertions, 1);
+            assert_eq!(bob.binary.added, 1);
+            assert_eq!(bob.binary.bytes, 24);
+            assert!(bob.categories.is_empty());
+        }
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn periods() {
+        let date = Utc.ymd(2020, 5, 14).and_hms(23, 30, 0);
+        let start = Period::Day.start(date);
+        assert_eq!(start, NaiveDate::from_ymd(2020, 5, 14));
+        assert_eq!(Period::Day.label(start), "2020-05-14");
+        let start = Period::Week.start(date);
+        assert_eq!(start, NaiveDate::from_ymd(2020, 5, 11));
+        assert_eq!(Period::Week.label(start), "2020-W20");
+        let start = Period::Month.start(date);
+        assert_eq!(start, NaiveDate::from_ymd(2020, 5, 1));
+        assert_eq!(Period::Month.label(start), "2020-05");
+        assert_eq!(Period::default(), Period::Week);
+        // ISO weeks can start in the previous year
+        let start = Period::Week.start(Utc.ymd(2021, 1, 2).and_hms(12, 0, 0));
+        assert_eq!(start, NaiveDate::from_ymd(2020, 12, 28));
+        assert_eq!(Period::Week.label(start), "2020-W53");
+    }
+
+    #[test]
+    fn timeline() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_timeline");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        let commits = [
+            ("Alice", Utc.ymd(2020, 5, 11).and_hms(9, 0, 0), "a\n"),
+            ("Alice", Utc.ymd(2020, 5, 13).and_hms(9, 0, 0), "a\nb\nc\n"),
+            ("Bob", Utc.ymd(2020, 5, 13).and_hms(18, 0, 0), "a\nb\n"),
+            ("Alice", Utc.ymd(2020, 6, 1).and_hms(9, 0, 0), "a\nd\n"),
+        ];
+        for (author, date, content) in commits.iter() {
+            std::fs::write(path.join("notes.txt"), content).unwrap();
+            let time = git2::Time::new(date.timestamp(), 0);
+            let signature =
+                git2::Signature::new(author, &format!("{}@example.com", author), &time)?;
+            commit_all_as(&repository, &signature, None)?;
+        }
+
+        let gd = GitDetective::open(&path)?;
+        let days = gd.timeline(Period::Day)?;
+        assert_eq!(days.len(), 3);
+        let may_13 = &days[&NaiveDate::from_ymd(2020, 5, 13)];
+        assert_eq!(may_13["Alice"].commits, 1);
+        assert_eq!(may_13["Alice"].insertions, 2);
+        assert_eq!(may_13["Bob"].deletions, 1);
+
+        let weeks = gd.timeline(Period::Week)?;
+        assert_eq!(weeks.len(), 2);
+        let week_20 = &weeks[&NaiveDate::from_ymd(2020, 5, 11)];
+        assert_eq!(week_20["Alice"].commits, 2);
+        assert_eq!(week_20["Alice"].insertions, 3);
+        assert_eq!(week_20["Bob"].commits, 1);
+
+        let months = gd.timeline(Period::Month)?;
+        let june = &months[&NaiveDate::from_ymd(2020, 6, 1)];
+        assert_eq!(june.len(), 1);
+        assert_eq!(june["Alice"].insertions, 1);
+        assert_eq!(june["Alice"].deletions, 1);
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn snapshots() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_snapshots");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        // Tags sort by name as "iteration-10" before "iteration-9", but were made after
+        let commits = [
+            ("Alice", 1_590_000_000, "a\nb\nc\n", "iteration-9"),
+            ("Bob", 1_590_086_400, "a\nd\ne\nf\n", "iteration-10"),
+        ];
+        for (author, timestamp, content, tag) in commits.iter() {
+            std::fs::write(path.join("notes.txt"), content).unwrap();
+            let time = git2::Time::new(*timestamp, 0);
+            let signature =
+                git2::Signature::new(author, &format!("{}@example.com", author), &time)?;
+            let id = commit_all_as(&repository, &signature, None)?;
+            let commit = repository.find_object(id, None)?;
+            repository.tag(tag, &commit, &signature, tag, false)?;
+        }
+        // A lightweight tag, and a branch named after the first tag pointing somewhere else
+        std::fs::write(path.join(".gitattributes"), "api.pb linguist-generated\n").unwrap();
+        std::fs::write(path.join("api.pb"), "x\ny\n").unwrap();
+        let time = git2::Time::new(1_590_172_800, 0);
+        let signature = git2::Signature::new("Carol", "carol@example.com", &time)?;
+        let id = commit_all_as(&repository, &signature, None)?;
+        let commit = repository.find_object(id, None)?;
+        repository.tag_lightweight("iteration-11", &commit, false)?;
+        repository.branch("iteration-9", &commit.peel_to_commit()?, false)?;
+
+        let mut gd = GitDetective::open(&path)?;
+        let snapshots = gd.snapshots()?;
+        let revisions: Vec<_> = snapshots
+            .iter()
+            .map(|snapshot| snapshot.revision.as_str())
+            .collect();
+        assert_eq!(revisions, ["iteration-9", "iteration-10", "iteration-11"]);
+        assert_eq!(snapshots[0].stats.total_lines(), 3);
+        assert_eq!(snapshots[0].delta["Alice"].lines, 3);
+        assert_eq!(snapshots[1].stats.total_lines(), 4);
+        assert_eq!(snapshots[1].delta["Alice"].lines, -2);
+        assert_eq!(snapshots[1].delta["Bob"].lines, 3);
+        assert_eq!(
+            snapshots[1].date,
+            Utc.timestamp_opt(1_590_086_400, 0).unwrap()
+        );
+        assert_eq!(snapshots[2].id, id);
+        assert_eq!(snapshots[2].delta["Carol"].lines, 1);
+        assert!(snapshots[2].categories.is_empty());
+
+        // Category deltas have the same buckets as the stats
+        gd.set_category_policy(CategoryPolicy::Separate);
+        let snapshots = gd.snapshots()?;
+        assert!(snapshots[1].categories.is_empty());
+        let generated = &snapshots[2].categories[&FileCategory::Generated];
+        assert_eq!(generated.len(), 1);
+        assert_eq!(generated["Carol"].lines, 2);
+        assert_eq!(snapshots[2].delta["Carol"].lines, 1);
+
+        // Revisions are snapshotted in the order given
+        let snapshots = gd.snapshots_at(["iteration-10", "iteration-9"])?;
+        assert_eq!(snapshots[0].delta["Bob"].lines, 3);
+        assert_eq!(snapshots[1].delta["Bob"].lines, -3);
+        assert_eq!(snapshots[1].delta["Alice"].lines, 2);
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() -> Result<(), Error> {
+        use serde_json::json;
+
+        let gd = GitDetective::open(".")?;
+        let diff_stats = gd.diff_stats()?;
+        let (contributor, stats) = diff_stats.iter().next().unwrap();
+        let json = serde_json::to_value(&diff_stats).unwrap();
+        assert_eq!(
+            json[contributor.name()]["insertions"],
+            json!(stats.insertions)
+        );
+        assert_eq!(json[contributor.name()]["whitespace_mode"], "sensitive");
+
+        let commit = gd.commits()?.next().unwrap();
+        let record = git_detective::CommitRecord::from(&commit);
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["id"], commit.id().to_string());
+        assert_eq!(json["date"], commit.date().to_rfc3339());
+
+        let file_status = git_detective::FileStatus {
+            path: String::from("src/lib.rs"),
+            status: git_detective::Status::INDEX_NEW | git_detective::Status::WT_MODIFIED,
+        };
+        let json = serde_json::to_value(&file_status).unwrap();
+        assert_eq!(json["status"], json!(["index_new", "wt_modified"]));
+        Ok(())
+    }
+
     #[test]
     fn checkout_tag() -> Result<(), Error> {
         let path = PathBuf::from("cursive");
@@ -174,7 +741,7 @@ mod git_detective_integration_tests {
 
     #[test]
     fn final_contributions() -> Result<(), Error> {
-        let mut gd = GitDetective::open(".")?;
+        let gd = GitDetective::open(".")?;
         let project_stats = gd.final_contributions()?;
         assert!(project_stats
             .contributors()
@@ -190,6 +757,301 @@ mod git_detective_integration_tests {
         Ok(())
     }
 
+    #[test]
+    fn final_contributions_single_thread() -> Result<(), Error> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let single_thread = pool.install(|| GitDetective::open(".")?.final_contributions())?;
+        let gd = GitDetective::open(".")?;
+        let project_stats = gd.final_contributions()?;
+        assert_eq!(single_thread.total_lines(), project_stats.total_lines());
+        assert_eq!(
+            single_thread.total_contribs_by_name("Nick Hackman"),
+            project_stats.total_contribs_by_name("Nick Hackman")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn final_contributions_blame_error() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_blame_error");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        std::fs::write(path.join("a.txt"), "a\n").unwrap();
+        let first = commit_all(&repository, "Alice", None)?.to_string();
+        std::fs::write(path.join("a.txt"), "a\nb\n").unwrap();
+        std::fs::write(path.join("b.txt"), "c\n").unwrap();
+        commit_all(&repository, "Bob", None)?;
+        std::fs::write(path.join("staged.txt"), "d\n").unwrap();
+        let mut index = repository.index()?;
+        index.add_path(std::path::Path::new("staged.txt"))?;
+        index.write()?;
+
+        let gd = GitDetective::open(&path)?;
+        assert_eq!(gd.final_contributions()?.total_lines(), 3);
+        // Blaming walks back to the first commit
+        std::fs::remove_file(
+            path.join(".git/objects")
+                .join(&first[..2])
+                .join(&first[2..]),
+        )
+        .unwrap();
+        let error = gd.final_contributions().err().unwrap();
+        assert_eq!(
+            discriminant(&error),
+            discriminant(&Error::GitError(git2::Error::from_str("")))
+        );
+        assert!(gd.final_contributions_at("HEAD").is_err());
+        assert!(gd.final_contributions_by_file().is_err());
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn blame_settings() -> Result<(), Error> {
+        let mut gd = GitDetective::open(".")?;
+        let (_, plain) = gd.final_contributions_file("src/lib.rs")?;
+        let mut settings = BlameSettings::new();
+        settings
+            .track_copies_same_file(true)
+            .track_copies_same_commit(true)
+            .min_match_characters(40);
+        gd.set_blame_settings(settings);
+        let (lang, tracked) = gd.final_contributions_file("src/lib.rs")?;
+        assert_eq!(lang, "Rust");
+        let total = |stats: &HashMap<Contributor, Stats>| -> usize {
+            stats.values().map(|stats| stats.lines).sum()
+        };
+        assert_eq!(total(&plain), total(&tracked));
+        assert!(tracked.contains_key("Nick Hackman"));
+        Ok(())
+    }
+
+    #[test]
+    fn track_copies() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_track_copies");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        let function = "fn doubled_sum(values: &[u32]) -> u32 {\n    \
+                        values.iter().map(|value| value * 2).sum()\n}\n";
+        std::fs::write(path.join("a.rs"), format!("fn keep() {{}}\n\n{}", function)).unwrap();
+        commit_all(&repository, "Alice", None)?;
+        // Bob moves Alice's function into another file and adds one of his own
+        std::fs::write(path.join("a.rs"), "fn keep() {}\n").unwrap();
+        std::fs::write(path.join("b.rs"), format!("{}fn added() {{}}\n", function)).unwrap();
+        commit_all(&repository, "Bob", None)?;
+
+        let mut gd = GitDetective::open(&path)?;
+        let (_, plain) = gd.final_contributions_file("b.rs")?;
+        assert_eq!(plain.len(), 1);
+        assert_eq!(plain["Bob"].lines, 4);
+
+        let mut settings = BlameSettings::new();
+        settings.track_copies_same_commit(true);
+        gd.set_blame_settings(settings.clone());
+        let (_, tracked) = gd.final_contributions_file("b.rs")?;
+        assert_eq!(tracked["Alice"].lines, 3);
+        assert_eq!(tracked["Bob"].lines, 1);
+
+        // The moved function has fewer alphanumeric characters than required
+        settings.min_match_characters(1000);
+        gd.set_blame_settings(settings);
+        let (_, short) = gd.final_contributions_file("b.rs")?;
+        assert_eq!(short.len(), 1);
+        assert_eq!(short["Bob"].lines, 4);
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn track_copies_chain() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_track_copies_chain");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        let function = "fn doubled_sum(values: &[u32]) -> u32 {\n    \
+                        values.iter().map(|value| value * 2).sum()\n}\n";
+        std::fs::write(path.join("a.rs"), function).unwrap();
+        commit_all(&repository, "Alice", None)?;
+        // Bob and then Carol move the function into a new file
+        std::fs::remove_file(path.join("a.rs")).unwrap();
+        std::fs::write(path.join("b.rs"), format!("fn bob() {{}}\n{}", function)).unwrap();
+        commit_all(&repository, "Bob", None)?;
+        std::fs::remove_file(path.join("b.rs")).unwrap();
+        std::fs::write(path.join("c.rs"), format!("{}fn carol() {{}}\n", function)).unwrap();
+        commit_all(&repository, "Carol", None)?;
+
+        let mut gd = GitDetective::open(&path)?;
+        let mut settings = BlameSettings::new();
+        settings.track_copies_same_commit(true);
+        gd.set_blame_settings(settings);
+        let (_, tracked) = gd.final_contributions_file("c.rs")?;
+        assert_eq!(tracked.len(), 2);
+        assert_eq!(tracked["Alice"].lines, 3);
+        assert_eq!(tracked["Carol"].lines, 1);
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn track_copies_bounded() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_track_copies_bounded");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        let line = "let copied_line = first_value + second_value;\n";
+        let mut content = String::from(line);
+        std::fs::write(path.join("a.rs"), &content).unwrap();
+        commit_all(&repository, "Alice", None)?;
+        // Every commit copies the first line, so each one is found in all of the earlier commits
+        for commit in 0..40 {
+            content.push_str(line);
+            std::fs::write(path.join("a.rs"), &content).unwrap();
+            commit_all(&repository, &format!("Copier {}", commit), None)?;
+        }
+
+        let mut gd = GitDetective::open(&path)?;
+        let mut settings = BlameSettings::new();
+        settings.track_copies_same_file(true);
+        gd.set_blame_settings(settings);
+        let (_, tracked) = gd.final_contributions_file("a.rs")?;
+        assert_eq!(tracked.len(), 1);
+        assert_eq!(tracked["Alice"].lines, 41);
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn ignore_revisions() -> Result<(), Error> {
+        let repository_path = std::env::temp_dir().join("git_detective_ignore_revisions");
+        let _ = remove_dir_all(&repository_path);
+        let repository = git2::Repository::init(&repository_path)?;
+        let lib = repository_path.join("lib.rs");
+        std::fs::write(&lib, "// Numbers\nfn one()->u32{1}\nfn two()->u32{2}\n").unwrap();
+        commit_all(&repository, "Alice", None)?;
+        // Bob adds a line above Alice's, so her lines move down as he reformats them
+        std::fs::write(
+            &lib,
+            "#![allow(dead_code)]\n// Numbers\nfn one() -> u32 { 1 }\nfn two() -> u32 { 2 }\n",
+        )
+        .unwrap();
+        let formatting = commit_all(&repository, "Bob", None)?;
+
+        let mut gd = GitDetective::open(&repository_path)?;
+        let (_, plain) = gd.final_contributions_file("lib.rs")?;
+        assert_eq!(plain["Alice"].lines, 1);
+        assert_eq!(plain["Bob"].lines, 3);
+
+        let mut settings = BlameSettings::new();
+        settings.ignore_revision(formatting);
+        gd.set_blame_settings(settings);
+        let (_, ignored) = gd.final_contributions_file("lib.rs")?;
+        assert_eq!(ignored["Alice"].lines, 3);
+        assert_eq!(ignored["Bob"].lines, 1);
+
+        gd.set_blame_settings(BlameSettings::new());
+        std::fs::write(
+            repository_path.join(".git-blame-ignore-revs"),
+            format!("# Formatting\n{}\n", formatting),
+        )
+        .unwrap();
+        let (_, ignored) = gd.final_contributions_file("lib.rs")?;
+        assert_eq!(ignored["Alice"].lines, 3);
+        assert_eq!(ignored["Bob"].lines, 1);
+
+        // Abbreviated object names are rejected instead of never matching
+        let abbreviated = formatting.to_string()[..12].to_string();
+        std::fs::write(
+            repository_path.join(".git-blame-ignore-revs"),
+            format!("{}\n", abbreviated),
+        )
+        .unwrap();
+        let error = gd.final_contributions_file("lib.rs").err().unwrap();
+        assert_eq!(
+            discriminant(&error),
+            discriminant(&Error::GitError(git2::Error::from_str("")))
+        );
+        assert!(error.to_string().contains(&abbreviated));
+        let removed = remove_dir_all(repository_path);
+        assert!(removed.is_ok());
+
+        let path = std::env::temp_dir().join("git_detective_integration_tests.ignore-revs");
+        std::fs::write(&path, "not a revision\n").unwrap();
+        let error = BlameSettings::new()
+            .ignore_revisions_file(&path)
+            .err()
+            .unwrap();
+        assert_eq!(
+            discriminant(&error),
+            discriminant(&Error::GitError(git2::Error::from_str("")))
+        );
+        let removed = std::fs::remove_file(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn whitespace_mode() -> Result<(), Error> {
+        let mut gd = GitDetective::open(".")?;
+        let sensitive: usize = gd
+            .diff_stats()?
+            .values()
+            .map(|stats| stats.insertions)
+            .sum();
+        gd.set_whitespace_mode(WhitespaceMode::Insensitive);
+        let diff_stats = gd.diff_stats()?;
+        assert!(diff_stats
+            .values()
+            .all(|stats| stats.whitespace_mode == WhitespaceMode::Insensitive));
+        let insensitive: usize = diff_stats.values().map(|stats| stats.insertions).sum();
+        assert!(insensitive <= sensitive);
+        let project_stats = gd.final_contributions()?;
+        assert_eq!(project_stats.whitespace_mode(), WhitespaceMode::Insensitive);
+        assert!(project_stats.total_lines() > 1000);
+        Ok(())
+    }
+
+    #[test]
+    fn final_contributions_at() -> Result<(), Error> {
+        let gd = GitDetective::open(".")?;
+        let project_stats = gd.final_contributions_at("HEAD")?;
+        assert!(project_stats
+            .contributors()
+            .any(|name| name == "Nick Hackman"));
+        assert!(project_stats.total_lines() > 1000);
+        let first_commit = gd.commits()?.last().unwrap().id().to_string();
+        let first_stats = gd.final_contributions_at(first_commit)?;
+        assert!(first_stats.total_lines() < project_stats.total_lines());
+        Ok(())
+    }
+
+    #[test]
+    fn bare_repository() -> Result<(), Error> {
+        let path = PathBuf::from("git_detective_bare_integration_tests");
+        let url = std::env::current_dir().unwrap();
+        git2::build::RepoBuilder::new()
+            .bare(true)
+            .clone(&url.to_string_lossy(), &path)?;
+        let gd = GitDetective::open(&path)?;
+        let list = gd.ls()?;
+        assert!(list.iter().any(|stat| &stat.path == "Cargo.toml"));
+        assert!(gd.contributors()?.contains("Nick Hackman"));
+        let project_stats = gd.final_contributions()?;
+        assert!(project_stats.total_lines() > 1000);
+        let (lang, _) = gd.final_contributions_file("src/lib.rs")?;
+        assert_eq!(lang, "Rust");
+        let commit = gd.commits()?.next().unwrap();
+        let error = gd.checkout(commit).err().unwrap();
+        assert_eq!(discriminant(&error), discriminant(&Error::BareRepository));
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
     #[test]
     fn branches() -> Result<(), Error> {
         let gd = GitDetective::open(".")?;
@@ -292,6 +1154,197 @@ mod git_detective_integration_tests {
         Ok(())
     }
 
+    #[test]
+    fn mailmap_file() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_integration_tests.mailmap");
+        std::fs::write(
+            &path,
+            "# Both identities are the same person\nNick Hackman <snickhackman@gmail.com> NickHackman <snickhackman@gmail.com>\n",
+        )
+        .unwrap();
+        let mut gd = GitDetective::open(".")?;
+        gd.mailmap_file(&path);
+        let contributors = gd.contributors()?;
+        assert!(contributors.contains("Nick Hackman"));
+        assert!(!contributors.contains("NickHackman"));
+        let stats_map = gd.diff_stats()?;
+        assert!(stats_map.contains_key("Nick Hackman"));
+        assert!(!stats_map.contains_key("NickHackman"));
+        let removed = std::fs::remove_file(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn mailmap_blob() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_mailmap_blob");
+        let bare_path = std::env::temp_dir().join("git_detective_mailmap_blob.git");
+        let _ = remove_dir_all(&path);
+        let _ = remove_dir_all(&bare_path);
+        let repository = git2::Repository::init(&path)?;
+        std::fs::create_dir(path.join("meta")).unwrap();
+        std::fs::write(path.join("meta/people"), "John Smith <john@example.com>\n").unwrap();
+        let nickname = git2::Signature::now("jsmith", "john@example.com")?;
+        commit_all_as(&repository, &nickname, None)?;
+        std::fs::write(path.join("notes.txt"), "a\n").unwrap();
+        let full_name = git2::Signature::now("John Smith", "john@example.com")?;
+        commit_all_as(&repository, &full_name, None)?;
+
+        let bare = git2::build::RepoBuilder::new()
+            .bare(true)
+            .clone(&path.to_string_lossy(), &bare_path)?;
+        let gd = GitDetective::open(&bare_path)?;
+        assert_eq!(gd.contributors()?.len(), 2);
+        bare.config()?.set_str("mailmap.blob", "HEAD:meta/people")?;
+        let gd = GitDetective::open(&bare_path)?;
+        let contributors = gd.contributors()?;
+        assert_eq!(contributors.len(), 1);
+        assert!(contributors.contains("John Smith"));
+        bare.config()?.set_str("mailmap.blob", "HEAD:meta")?;
+        assert!(gd.contributors().is_err());
+        assert!(remove_dir_all(path).is_ok());
+        assert!(remove_dir_all(bare_path).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn identity_resolver() -> Result<(), Error> {
+        let mut resolver = IdentityResolver::new();
+        resolver.alias(IdentityMatcher::regex("^Nick ?Hackman$")?, "Nick Hackman");
+        let mut gd = GitDetective::open(".")?;
+        gd.set_identity_resolver(resolver);
+        let contributors = gd.contributors()?;
+        assert!(!contributors.contains("NickHackman"));
+        let nh = contributors.get("Nick Hackman").unwrap();
+        assert!(nh
+            .emails()
+            .iter()
+            .any(|email| email.contains("snickhackman")));
+        let contrib_files = gd.files_contributed_to()?;
+        assert!(!contrib_files.contains_key("NickHackman"));
+        assert!(contrib_files
+            .get("Nick Hackman")
+            .unwrap()
+            .contains(&PathBuf::from("LICENSE")));
+        Ok(())
+    }
+
+    #[test]
+    fn commit_counts() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_commit_counts");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        std::fs::write(path.join(".mailmap"), "John Smith <john@example.com>\n").unwrap();
+        let nickname = git2::Signature::now("jsmith", "john@example.com")?;
+        commit_all_as(&repository, &nickname, None)?;
+        std::fs::write(path.join("notes.txt"), "a\n").unwrap();
+        let full_name = git2::Signature::now("John Smith", "john@example.com")?;
+        commit_all_as(&repository, &full_name, None)?;
+        std::fs::write(path.join("notes.txt"), "a\nb\n").unwrap();
+        let id = commit_all(&repository, "Alice", None)?;
+        let message = "Pair\n\nCo-authored-by: jsmith <john@example.com>\n";
+        repository
+            .find_commit(id)?
+            .amend(Some("HEAD"), None, None, None, Some(message), None)?;
+
+        let mut gd = GitDetective::open(&path)?;
+        let counts = gd.commit_counts()?;
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["John Smith"], 2);
+        assert_eq!(counts["Alice"], 1);
+        gd.set_credit_policy(CreditPolicy::FullCredit);
+        let counts = gd.commit_counts()?;
+        assert_eq!(counts["John Smith"], 3);
+        assert_eq!(counts["Alice"], 1);
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn credit_policy_split_evenly() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("git_detective_credit_policy");
+        let _ = remove_dir_all(&path);
+        let repository = git2::Repository::init(&path)?;
+        std::fs::write(path.join("notes.txt"), "a\nb\nc\n").unwrap();
+        commit_all(&repository, "Alice", None)?;
+        std::fs::write(path.join("notes.txt"), "a\nd\ne\nf\ng\n").unwrap();
+        std::fs::write(path.join("logo.png"), [0u8, 1, 2, 3, 4]).unwrap();
+        let id = commit_all(&repository, "Alice", None)?;
+        // Only the trailer block credits co-authors, not prose in the body
+        let message = "Rewrite notes\n\n\
+                       Co-authored-by: see below <nobody@example.com>\n\
+                       was a mistake\n\n\
+                       Co-authored-by: Bob <bob@example.com>\n\
+                       co-authored-by: Carol <carol@example.com>\n";
+        repository
+            .find_commit(id)?
+            .amend(Some("HEAD"), None, None, None, Some(message), None)?;
+
+        let mut gd = GitDetective::open(&path)?;
+        let co_authors: Vec<_> = gd
+            .commits()?
+            .next()
+            .unwrap()
+            .co_authors()
+            .iter()
+            .map(|co_author| co_author.name().unwrap())
+            .collect();
+        assert_eq!(co_authors, ["Bob", "Carol"]);
+
+        let author_only = gd.diff_stats()?;
+        assert_eq!(author_only.len(), 1);
+        assert_eq!(author_only["Alice"].insertions, 7);
+        assert_eq!(author_only["Alice"].deletions, 2);
+
+        // 4 insertions and 2 deletions split three ways, remainders go to the author first
+        gd.set_credit_policy(CreditPolicy::SplitEvenly);
+        let split = gd.diff_stats()?;
+        assert_eq!(split.len(), 3);
+        assert_eq!(split["Alice"].insertions, 3 + 2);
+        assert_eq!(split["Alice"].deletions, 1);
+        assert_eq!(split["Bob"].insertions, 1);
+        assert_eq!(split["Bob"].deletions, 1);
+        assert_eq!(split["Carol"].insertions, 1);
+        assert_eq!(split["Carol"].deletions, 0);
+        let insertions: usize = split.values().map(|stats| stats.insertions).sum();
+        assert_eq!(insertions, 7);
+        // Binary files are events, only their 5 bytes are split
+        for stats in split.values() {
+            assert_eq!(stats.binary.added, 1);
+        }
+        assert_eq!(split["Alice"].binary.bytes, 2);
+        assert_eq!(split["Bob"].binary.bytes, 2);
+        assert_eq!(split["Carol"].binary.bytes, 1);
+
+        gd.set_credit_policy(CreditPolicy::FullCredit);
+        let full = gd.diff_stats()?;
+        assert_eq!(full.len(), 3);
+        assert_eq!(full["Alice"].insertions, 7);
+        assert_eq!(full["Bob"].insertions, 4);
+        assert_eq!(full["Carol"].insertions, 4);
+        assert_eq!(full["Carol"].deletions, 2);
+        let removed = remove_dir_all(path);
+        assert!(removed.is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn history_query() -> Result<(), Error> {
+        let gd = GitDetective::open(".")?;
+        let mut last = HistoryQuery::new();
+        last.range("HEAD~1..HEAD").first_parent(true);
+        assert_eq!(gd.commits_in(&last)?.count(), 1);
+        let mut all = HistoryQuery::new();
+        all.all(true);
+        assert!(gd.commits_in(&all)?.count() >= gd.commits()?.count());
+        let mut future = HistoryQuery::new();
+        future.since(Utc::now() + chrono::Duration::days(1));
+        assert_eq!(gd.commits_in(&future)?.count(), 0);
+        assert!(gd.diff_stats_in(&future)?.is_empty());
+        Ok(())
+    }
+
     #[test]
     fn files_contributed_to() -> Result<(), Error> {
         let gd = GitDetective::open(".")?;